@@ -510,6 +510,14 @@ where
 /// - The subject buffer can be used to further randomize the hash.
 /// - Within the same execution returns the same random hash for the same subject.
 ///
+/// # Security
+///
+/// The randomness is derived from the randomness source of the chain which is
+/// based on past blocks. It is known ahead of time to the block producers and
+/// can to some extent be influenced by them. Therefore it must not be relied
+/// upon in situations where a lot of value is at stake, e.g. for lotteries with
+/// big prize pools.
+///
 /// # Errors
 ///
 /// If the returned value cannot be properly decoded.
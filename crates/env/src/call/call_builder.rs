@@ -46,12 +46,6 @@ where
     exec_input: ExecutionInput<Args>,
 }
 
-#[cfg(
-    // We do not currently support cross-contract calling in the off-chain
-    // environment so we do not have to provide these getters in case of
-    // off-chain environment compilation.
-    all(not(feature = "std"), target_arch = "wasm32")
-)]
 impl<E, Args, R> CallParams<E, Args, R>
 where
     E: Environment,
@@ -62,6 +56,23 @@ where
         &self.callee
     }
 
+    /// Returns the execution input.
+    #[inline]
+    pub(crate) fn exec_input(&self) -> &ExecutionInput<Args> {
+        &self.exec_input
+    }
+}
+
+#[cfg(
+    // The off-chain environment does not meter gas or move the transferred
+    // value of cross-contract calls so we do not have to provide these getters
+    // in case of off-chain environment compilation.
+    all(not(feature = "std"), target_arch = "wasm32")
+)]
+impl<E, Args, R> CallParams<E, Args, R>
+where
+    E: Environment,
+{
     /// Returns the chosen gas limit for the called contract execution.
     #[inline]
     pub(crate) fn gas_limit(&self) -> u64 {
@@ -73,12 +84,6 @@ where
     pub(crate) fn transferred_value(&self) -> &E::Balance {
         &self.transferred_value
     }
-
-    /// Returns the execution input.
-    #[inline]
-    pub(crate) fn exec_input(&self) -> &ExecutionInput<Args> {
-        &self.exec_input
-    }
 }
 
 impl<E, Args> CallParams<E, Args, ()>
@@ -120,9 +125,9 @@ where
 ///
 /// # Example
 ///
-/// **Note:** The shown examples panic because there is currently no cross-calling
-///           support in the off-chain testing environment. However, this code
-///           should work fine in on-chain environments.
+/// **Note:** The shown examples panic because there is no handler registered
+///           for the callee in the off-chain testing environment. However, this
+///           code should work fine in on-chain environments.
 ///
 /// ## Example 1: No Return Value
 ///
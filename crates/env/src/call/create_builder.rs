@@ -60,12 +60,6 @@ where
     return_type: ReturnType<R>,
 }
 
-#[cfg(
-    // We do not currently support cross-contract instantiation in the off-chain
-    // environment so we do not have to provide these getters in case of
-    // off-chain environment compilation.
-    all(not(feature = "std"), target_arch = "wasm32")
-)]
 impl<E, Args, R> CreateParams<E, Args, R>
 where
     E: Environment,
@@ -76,6 +70,23 @@ where
         &self.code_hash
     }
 
+    /// The raw encoded input data.
+    #[inline]
+    pub(crate) fn exec_input(&self) -> &ExecutionInput<Args> {
+        &self.exec_input
+    }
+}
+
+#[cfg(
+    // The off-chain environment does not meter gas or move the endowment of
+    // cross-contract instantiations so we do not have to provide these getters
+    // in case of off-chain environment compilation.
+    all(not(feature = "std"), target_arch = "wasm32")
+)]
+impl<E, Args, R> CreateParams<E, Args, R>
+where
+    E: Environment,
+{
     /// The gas limit for the contract instantiation.
     #[inline]
     pub(crate) fn gas_limit(&self) -> u64 {
@@ -87,12 +98,6 @@ where
     pub(crate) fn endowment(&self) -> &E::Balance {
         &self.endowment
     }
-
-    /// The raw encoded input data.
-    #[inline]
-    pub(crate) fn exec_input(&self) -> &ExecutionInput<Args> {
-        &self.exec_input
-    }
 }
 
 impl<E, Args, R> CreateParams<E, Args, R>
@@ -164,9 +169,9 @@ where
 ///     .unwrap();
 /// ```
 ///
-/// **Note:** The shown example panics because there is currently no cross-calling
-///           support in the off-chain testing environment. However, this code
-///           should work fine in on-chain environments.
+/// **Note:** The shown example panics because there is no handler registered
+///           for the code hash in the off-chain testing environment. However,
+///           this code should work fine in on-chain environments.
 #[allow(clippy::type_complexity)]
 pub fn build_create<E, R>() -> CreateBuilder<
     E,
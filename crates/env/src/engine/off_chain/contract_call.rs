@@ -0,0 +1,110 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Error,
+    Result,
+};
+use std::collections::HashMap;
+
+/// The encoding of a callee account ID or of an instantiated code hash.
+type EncodedKey = Vec<u8>;
+
+/// A raw contract call or instantiation handler.
+///
+/// Receives the encoded selector and arguments and returns the encoded
+/// return value of the called message or the encoded account ID of the
+/// instantiated contract respectively.
+type ContractCallFn = Box<dyn FnMut(Vec<u8>) -> Result<Vec<u8>>>;
+
+/// Cross-contract call and instantiation handler.
+///
+/// More generically a mapping from callee accounts and code hashes to
+/// functions from bytes to bytes.
+pub struct ContractCallHandler {
+    /// The handlers registered for calls to callee accounts.
+    calls: HashMap<EncodedKey, ContractCallFn>,
+    /// The handlers registered for instantiations of code hashes.
+    instantiations: HashMap<EncodedKey, ContractCallFn>,
+}
+
+impl ContractCallHandler {
+    /// Creates a new contract call handler.
+    ///
+    /// Initialized without any registered callees or code hashes.
+    pub fn new() -> Self {
+        Self {
+            calls: HashMap::new(),
+            instantiations: HashMap::new(),
+        }
+    }
+
+    /// Resets the contract call handler to uninitialized state.
+    pub fn reset(&mut self) {
+        self.calls.clear();
+        self.instantiations.clear();
+    }
+
+    /// Registers the handler for calls to the given encoded callee.
+    ///
+    /// Replaces the handler that has previously been registered for the same
+    /// callee if any.
+    pub fn register_call<F>(&mut self, callee: EncodedKey, handler: F)
+    where
+        F: FnMut(Vec<u8>) -> Result<Vec<u8>> + 'static,
+    {
+        self.calls.insert(callee, Box::new(handler));
+    }
+
+    /// Registers the handler for instantiations of the given encoded code hash.
+    ///
+    /// Replaces the handler that has previously been registered for the same
+    /// code hash if any.
+    pub fn register_instantiate<F>(&mut self, code_hash: EncodedKey, handler: F)
+    where
+        F: FnMut(Vec<u8>) -> Result<Vec<u8>> + 'static,
+    {
+        self.instantiations.insert(code_hash, Box::new(handler));
+    }
+
+    /// Calls the handler registered for the given encoded callee with the given input.
+    ///
+    /// Upon success returns the encoded output of the handler.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no handler registered for the callee.
+    /// - If the handler of the callee returned an error.
+    pub fn call(&mut self, callee: &[u8], input: Vec<u8>) -> Result<Vec<u8>> {
+        match self.calls.get_mut(callee) {
+            Some(handler) => handler(input),
+            None => Err(Error::NotCallable),
+        }
+    }
+
+    /// Calls the handler registered for the given encoded code hash with the given input.
+    ///
+    /// Upon success returns the encoded account ID of the instantiated contract.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no handler registered for the code hash.
+    /// - If the handler of the code hash returned an error.
+    pub fn instantiate(&mut self, code_hash: &[u8], input: Vec<u8>) -> Result<Vec<u8>> {
+        match self.instantiations.get_mut(code_hash) {
+            Some(handler) => handler(input),
+            None => Err(Error::CodeNotFound),
+        }
+    }
+}
@@ -89,6 +89,19 @@ impl ExecContext {
         self.gas.decode().map_err(Into::into)
     }
 
+    /// Sets the gas left for the execution to the given amount.
+    ///
+    /// # Note
+    ///
+    /// This is mainly used to simulate gas consumption in the off-chain
+    /// environment which does not meter gas on its own.
+    pub fn set_gas<T>(&mut self, new_gas: T::Balance) -> Result<()>
+    where
+        T: Environment,
+    {
+        self.gas.assign(&new_gas).map_err(Into::into)
+    }
+
     /// Returns the call data.
     #[allow(
         dead_code,
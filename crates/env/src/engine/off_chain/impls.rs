@@ -67,6 +67,25 @@ impl EnvInstance {
             .get_account_off_mut(&callee)
             .expect("callee account does not exist")
     }
+
+    /// Calls the handler registered for the callee of the given call parameters.
+    ///
+    /// Returns the decoded output of the handler.
+    fn invoke_contract_impl<T, Args, RetType, R>(
+        &mut self,
+        params: &CallParams<T, Args, RetType>,
+    ) -> Result<R>
+    where
+        T: Environment,
+        Args: scale::Encode,
+        R: scale::Decode,
+    {
+        let enc_callee = scale::Encode::encode(params.callee());
+        let enc_input = scale::Encode::encode(params.exec_input());
+        let output = self.contract_call_handler.call(&enc_callee, enc_input)?;
+        let decoded = scale::Decode::decode(&mut &output[..])?;
+        Ok(decoded)
+    }
 }
 
 impl CryptoHash for Blake2x128 {
@@ -375,36 +394,42 @@ impl TypedEnvBackend for EnvInstance {
 
     fn invoke_contract<T, Args>(
         &mut self,
-        _call_params: &CallParams<T, Args, ()>,
+        call_params: &CallParams<T, Args, ()>,
     ) -> Result<()>
     where
         T: Environment,
         Args: scale::Encode,
     {
-        unimplemented!("off-chain environment does not support contract invocation")
+        self.invoke_contract_impl(call_params)
     }
 
     fn eval_contract<T, Args, R>(
         &mut self,
-        _call_params: &CallParams<T, Args, ReturnType<R>>,
+        call_params: &CallParams<T, Args, ReturnType<R>>,
     ) -> Result<R>
     where
         T: Environment,
         Args: scale::Encode,
         R: scale::Decode,
     {
-        unimplemented!("off-chain environment does not support contract evaluation")
+        self.invoke_contract_impl(call_params)
     }
 
     fn instantiate_contract<T, Args, C>(
         &mut self,
-        _params: &CreateParams<T, Args, C>,
+        params: &CreateParams<T, Args, C>,
     ) -> Result<T::AccountId>
     where
         T: Environment,
         Args: scale::Encode,
     {
-        unimplemented!("off-chain environment does not support contract instantiation")
+        let enc_code_hash = scale::Encode::encode(params.code_hash());
+        let enc_input = scale::Encode::encode(params.exec_input());
+        let enc_account_id = self
+            .contract_call_handler
+            .instantiate(&enc_code_hash, enc_input)?;
+        let account_id = scale::Decode::decode(&mut &enc_account_id[..])?;
+        Ok(account_id)
     }
 
     fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
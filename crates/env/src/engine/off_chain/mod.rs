@@ -15,6 +15,7 @@
 mod call_data;
 #[cfg(feature = "ink-unstable-chain-extensions")]
 mod chain_extension;
+mod contract_call;
 mod db;
 mod hashing;
 mod impls;
@@ -27,6 +28,7 @@ mod tests;
 
 #[cfg(feature = "ink-unstable-chain-extensions")]
 use self::chain_extension::ChainExtensionHandler;
+use self::contract_call::ContractCallHandler;
 pub use self::{
     call_data::CallData,
     db::{
@@ -91,6 +93,8 @@ pub struct EnvInstance {
     /// Handler for registered chain extensions.
     #[cfg(feature = "ink-unstable-chain-extensions")]
     chain_extension_handler: ChainExtensionHandler,
+    /// Handler for registered cross-contract calls and instantiations.
+    contract_call_handler: ContractCallHandler,
     /// Emitted events recorder.
     emitted_events: EmittedEventsRecorder,
     /// Set to true to disable clearing storage
@@ -108,6 +112,7 @@ impl EnvInstance {
             console: Console::new(),
             #[cfg(feature = "ink-unstable-chain-extensions")]
             chain_extension_handler: ChainExtensionHandler::new(),
+            contract_call_handler: ContractCallHandler::new(),
             emitted_events: EmittedEventsRecorder::new(),
             clear_storage_disabled: false,
         }
@@ -140,6 +145,7 @@ impl EnvInstance {
         self.console.reset();
         #[cfg(feature = "ink-unstable-chain-extensions")]
         self.chain_extension_handler.reset();
+        self.contract_call_handler.reset();
         self.emitted_events.reset();
         self.clear_storage_disabled = false;
     }
@@ -211,7 +217,7 @@ impl EnvInstance {
                 .caller(default_accounts.alice)
                 .callee(contract_account_id)
                 .gas(T::Balance::from(500_000u32))
-                .transferred_value(T::Balance::from(500u32))
+                .transferred_value(T::Balance::zero())
                 .call_data(CallData::new(Selector::new(selector_bytes_for_call)))
                 .finish(),
         );
@@ -194,6 +194,60 @@ where
     })
 }
 
+/// Registers a handler for cross-contract calls to the given callee.
+///
+/// The handler receives the encoded selector and arguments of every call to
+/// the callee and returns the encoded return value of the called message.
+/// Returning an error such as [`Error::CalleeReverted`](`crate::Error::CalleeReverted`)
+/// lets the call fail without affecting the caller.
+///
+/// # Note
+///
+/// - Calls to a callee without a registered handler fail with
+///   [`Error::NotCallable`](`crate::Error::NotCallable`).
+/// - The handler cannot interact with the off-chain environment itself and
+///   the transferred value of the call is not moved between the accounts.
+pub fn register_contract_handler<T, F>(callee: T::AccountId, handler: F)
+where
+    T: Environment,
+    F: FnMut(Vec<u8>) -> Result<Vec<u8>> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .contract_call_handler
+            .register_call(scale::Encode::encode(&callee), handler);
+    })
+}
+
+/// Registers a handler for instantiations of the given code hash.
+///
+/// The handler receives the encoded selector and arguments of the constructor
+/// for every instantiation of the code hash and returns the account ID of the
+/// instantiated contract. Returning an error such as
+/// [`Error::NewContractNotFunded`](`crate::Error::NewContractNotFunded`) lets
+/// the instantiation fail without affecting the caller.
+///
+/// # Note
+///
+/// - Instantiations of a code hash without a registered handler fail with
+///   [`Error::CodeNotFound`](`crate::Error::CodeNotFound`).
+/// - The handler cannot interact with the off-chain environment itself and
+///   the endowment of the instantiation is not moved between the accounts.
+pub fn register_instantiate_handler<T, F>(code_hash: T::Hash, mut handler: F)
+where
+    T: Environment,
+    F: FnMut(Vec<u8>) -> Result<T::AccountId> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.contract_call_handler.register_instantiate(
+            scale::Encode::encode(&code_hash),
+            move |input| {
+                handler(input).map(|account_id| scale::Encode::encode(&account_id))
+            },
+        );
+    })
+}
+
 /// Set the entropy hash of the current block.
 ///
 /// # Note
@@ -209,6 +263,22 @@ where
     .map_err(Into::into)
 }
 
+/// Sets the gas left for the current contract execution.
+///
+/// # Note
+///
+/// This allows to control what [`crate::gas_left`] returns since the
+/// off-chain environment does not consume gas on its own.
+pub fn set_gas_left<T>(gas_left: T::Balance) -> Result<()>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.exec_context_mut()?.set_gas::<T>(gas_left)
+    })
+    .map_err(Into::into)
+}
+
 /// Update the [ChainSpec](`crate::test::ChainSpec`) for the test environment
 pub fn update_chain_spec<F>(f: F) -> Result<()>
 where
@@ -90,3 +90,396 @@ fn gas_price() -> crate::Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn caller_works() -> crate::Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|default_accounts| {
+        // The default execution context is called by alice.
+        assert_eq!(
+            crate::caller::<crate::DefaultEnvironment>(),
+            Ok(default_accounts.alice)
+        );
+        // A pushed execution context changes the caller.
+        let callee =
+            crate::test::get_current_contract_account_id::<crate::DefaultEnvironment>()?;
+        crate::test::push_execution_context::<crate::DefaultEnvironment>(
+            default_accounts.bob,
+            callee,
+            1_000_000,
+            0,
+            crate::test::CallData::new(crate::call::Selector::new([0x00; 4])),
+        );
+        assert_eq!(
+            crate::caller::<crate::DefaultEnvironment>(),
+            Ok(default_accounts.bob)
+        );
+        // Popping the execution context restores the previous caller.
+        crate::test::pop_execution_context();
+        assert_eq!(
+            crate::caller::<crate::DefaultEnvironment>(),
+            Ok(default_accounts.alice)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn block_number_and_timestamp_advance() -> crate::Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        const UNLOCK_BLOCK: u64 = 3;
+        let is_unlocked = || -> crate::Result<bool> {
+            Ok(crate::block_number::<crate::DefaultEnvironment>()? >= UNLOCK_BLOCK)
+        };
+        // Both values are stable within a single block.
+        let number = crate::block_number::<crate::DefaultEnvironment>()?;
+        let timestamp = crate::block_timestamp::<crate::DefaultEnvironment>()?;
+        assert_eq!(crate::block_number::<crate::DefaultEnvironment>()?, number);
+        assert_eq!(
+            crate::block_timestamp::<crate::DefaultEnvironment>()?,
+            timestamp
+        );
+        for _ in number..(UNLOCK_BLOCK - 1) {
+            crate::test::advance_block::<crate::DefaultEnvironment>()?;
+        }
+        // One block before the boundary the lock still holds.
+        assert_eq!(
+            crate::block_number::<crate::DefaultEnvironment>()?,
+            UNLOCK_BLOCK - 1
+        );
+        assert!(!is_unlocked()?);
+        crate::test::advance_block::<crate::DefaultEnvironment>()?;
+        // At the boundary the lock is released.
+        assert_eq!(
+            crate::block_number::<crate::DefaultEnvironment>()?,
+            UNLOCK_BLOCK
+        );
+        assert!(is_unlocked()?);
+        assert!(crate::block_timestamp::<crate::DefaultEnvironment>()? > timestamp);
+        Ok(())
+    })
+}
+
+#[test]
+fn transferred_balance_works() -> crate::Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|default_accounts| {
+        let total_key = Key::from([0x42; 32]);
+        // A payable message that adds the transferred value to a stored total.
+        let donate = || -> core::result::Result<u128, &'static str> {
+            let value = crate::transferred_balance::<crate::DefaultEnvironment>()
+                .expect("cannot query transferred balance");
+            if value == 0 {
+                return Err("cannot donate nothing")
+            }
+            let total = crate::get_contract_storage::<u128>(&total_key)
+                .expect("cannot decode donated total")
+                .unwrap_or(0)
+                + value;
+            crate::set_contract_storage(&total_key, &total);
+            Ok(total)
+        };
+        let callee =
+            crate::test::get_current_contract_account_id::<crate::DefaultEnvironment>()?;
+        let call_with_value = |value: u128| {
+            crate::test::push_execution_context::<crate::DefaultEnvironment>(
+                default_accounts.bob,
+                callee,
+                1_000_000,
+                value,
+                crate::test::CallData::new(crate::call::Selector::new([0x00; 4])),
+            );
+            let result = donate();
+            crate::test::pop_execution_context();
+            result
+        };
+        // The default execution context does not transfer any value.
+        assert_eq!(crate::transferred_balance::<crate::DefaultEnvironment>(), Ok(0));
+        assert_eq!(donate(), Err("cannot donate nothing"));
+        assert_eq!(call_with_value(10), Ok(10));
+        assert_eq!(call_with_value(0), Err("cannot donate nothing"));
+        assert_eq!(call_with_value(5), Ok(15));
+        // The transferred value does not leak into the next execution context.
+        assert_eq!(crate::transferred_balance::<crate::DefaultEnvironment>(), Ok(0));
+        assert_eq!(
+            crate::balance::<crate::DefaultEnvironment>(),
+            crate::test::get_account_balance::<crate::DefaultEnvironment>(callee)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn gas_left_works() -> crate::Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        const GAS_PER_ITEM: u128 = 100;
+        const GAS_RESERVE: u128 = 150;
+        const ITEMS: u32 = 10;
+        let cursor_key = Key::from([0x42; 32]);
+        // Processes items while enough gas is left and persists a cursor
+        // to the first unprocessed item for the next call.
+        let process = || -> crate::Result<u32> {
+            let mut cursor = crate::get_contract_storage::<u32>(&cursor_key)?
+                .unwrap_or(0);
+            while cursor < ITEMS
+                && crate::gas_left::<crate::DefaultEnvironment>()? > GAS_RESERVE
+            {
+                cursor += 1;
+                let gas_left = crate::gas_left::<crate::DefaultEnvironment>()?;
+                crate::test::set_gas_left::<crate::DefaultEnvironment>(
+                    gas_left - GAS_PER_ITEM,
+                )?;
+            }
+            crate::set_contract_storage(&cursor_key, &cursor);
+            Ok(cursor)
+        };
+        // Enough gas to process 4 items before hitting the reserve.
+        crate::test::set_gas_left::<crate::DefaultEnvironment>(550)?;
+        assert_eq!(crate::gas_left::<crate::DefaultEnvironment>(), Ok(550));
+        assert_eq!(process()?, 4);
+        assert_eq!(crate::gas_left::<crate::DefaultEnvironment>(), Ok(150));
+        // A later call resumes from the persisted cursor.
+        crate::test::set_gas_left::<crate::DefaultEnvironment>(10_000)?;
+        assert_eq!(process()?, ITEMS);
+        Ok(())
+    })
+}
+
+#[test]
+fn recorded_events_works() -> crate::Result<()> {
+    use crate::topics::{
+        state,
+        Topics,
+        TopicsBuilder,
+        TopicsBuilderBackend,
+    };
+    use scale::Decode as _;
+
+    /// An event with a single topic.
+    #[derive(scale::Encode, scale::Decode, Debug, PartialEq)]
+    struct Incremented {
+        by: u32,
+        new_value: u32,
+    }
+
+    impl Topics for Incremented {
+        type RemainingTopics = [state::HasRemainingTopics; 1];
+
+        fn topics<E, B>(
+            &self,
+            builder: TopicsBuilder<state::Uninit, E, B>,
+        ) -> <B as TopicsBuilderBackend<E>>::Output
+        where
+            E: crate::Environment,
+            B: TopicsBuilderBackend<E>,
+        {
+            builder.build::<Self>().push_topic(&self.by).finish()
+        }
+    }
+
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // A message that emits an event for every increment.
+        let mut value = 0;
+        let mut increment = |by: u32| {
+            value += by;
+            crate::emit_event::<crate::DefaultEnvironment, _>(Incremented {
+                by,
+                new_value: value,
+            });
+        };
+        increment(5);
+        increment(7);
+        let events = crate::test::recorded_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.topics.len() == 1));
+        assert_ne!(events[0].topics, events[1].topics);
+        assert_eq!(
+            Incremented::decode(&mut &events[0].data[..]),
+            Ok(Incremented {
+                by: 5,
+                new_value: 5,
+            })
+        );
+        assert_eq!(
+            Incremented::decode(&mut &events[1].data[..]),
+            Ok(Incremented {
+                by: 7,
+                new_value: 12,
+            })
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn contract_call_works() -> crate::Result<()> {
+    use crate::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    use scale::{
+        Decode as _,
+        Encode as _,
+    };
+
+    const DOUBLE: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xEF];
+
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|default_accounts| {
+        // A callee that doubles its argument and reverts on overflow.
+        crate::test::register_contract_handler::<crate::DefaultEnvironment, _>(
+            default_accounts.django,
+            |input: Vec<u8>| {
+                let mut input = &input[..];
+                assert_eq!(Selector::decode(&mut input)?, Selector::new(DOUBLE));
+                let value = u32::decode(&mut input)?;
+                let doubled = value.checked_mul(2).ok_or(crate::Error::CalleeReverted)?;
+                Ok(doubled.encode())
+            },
+        );
+        let double = |callee, value: u32| {
+            build_call::<crate::DefaultEnvironment>()
+                .callee(callee)
+                .gas_limit(5000)
+                .transferred_value(0)
+                .exec_input(ExecutionInput::new(Selector::new(DOUBLE)).push_arg(value))
+                .returns::<ReturnType<u32>>()
+                .fire()
+        };
+        assert_eq!(double(default_accounts.django, 21), Ok(42));
+        // The failure of the callee surfaces as an error to the caller.
+        assert_eq!(
+            double(default_accounts.django, u32::MAX),
+            Err(crate::Error::CalleeReverted)
+        );
+        // Accounts without a registered handler cannot be called.
+        assert_eq!(
+            double(default_accounts.eve, 21),
+            Err(crate::Error::NotCallable)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn contract_instantiation_works() -> crate::Result<()> {
+    use crate::{
+        call::{
+            build_create,
+            ExecutionInput,
+            FromAccountId,
+            Selector,
+        },
+        AccountId,
+        Hash,
+    };
+    use scale::Decode as _;
+
+    const NEW: [u8; 4] = [0xCA, 0xFE, 0xBA, 0xBE];
+
+    /// A reference to an instantiated escrow contract.
+    struct Escrow(AccountId);
+
+    impl FromAccountId<crate::DefaultEnvironment> for Escrow {
+        fn from_account_id(account_id: AccountId) -> Self {
+            Self(account_id)
+        }
+    }
+
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let escrow_code = Hash::from([0x01; 32]);
+        let children_key = Key::from([0x42; 32]);
+        // Derives the account ID of a new escrow from its deal ID and refuses
+        // to instantiate escrows for deal zero.
+        crate::test::register_instantiate_handler::<crate::DefaultEnvironment, _>(
+            escrow_code,
+            |input: Vec<u8>| {
+                let mut input = &input[..];
+                assert_eq!(Selector::decode(&mut input)?, Selector::new(NEW));
+                match u8::decode(&mut input)? {
+                    0 => Err(crate::Error::NewContractNotFunded),
+                    deal => Ok(AccountId::from([deal; 32])),
+                }
+            },
+        );
+        // A factory message that instantiates a new escrow for the given deal
+        // and stores its address among the children of the factory.
+        let new_escrow = |code_hash, deal: u8| -> crate::Result<AccountId> {
+            let escrow = build_create::<crate::DefaultEnvironment, Escrow>()
+                .code_hash(code_hash)
+                .gas_limit(4000)
+                .endowment(25)
+                .exec_input(ExecutionInput::new(Selector::new(NEW)).push_arg(deal))
+                .params()
+                .instantiate()?;
+            let mut children = crate::get_contract_storage::<Vec<AccountId>>(
+                &children_key,
+            )?
+            .unwrap_or_default();
+            children.push(escrow.0);
+            crate::set_contract_storage(&children_key, &children);
+            Ok(escrow.0)
+        };
+        assert_eq!(new_escrow(escrow_code, 1), Ok(AccountId::from([0x01; 32])));
+        assert_eq!(new_escrow(escrow_code, 2), Ok(AccountId::from([0x02; 32])));
+        // Failed instantiations surface as errors and store nothing.
+        assert_eq!(
+            new_escrow(escrow_code, 0),
+            Err(crate::Error::NewContractNotFunded)
+        );
+        assert_eq!(
+            new_escrow(Hash::from([0x02; 32]), 3),
+            Err(crate::Error::CodeNotFound)
+        );
+        assert_eq!(
+            crate::get_contract_storage::<Vec<AccountId>>(&children_key),
+            Ok(Some(vec![
+                AccountId::from([0x01; 32]),
+                AccountId::from([0x02; 32])
+            ]))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn random_works() -> crate::Result<()> {
+    use crate::Hash;
+
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|default_accounts| {
+        let participants = [
+            default_accounts.alice,
+            default_accounts.bob,
+            default_accounts.charlie,
+            default_accounts.django,
+        ];
+        // Picks the winner of a raffle from the random hash of the round.
+        let pick_winner = |round: u32| -> crate::Result<crate::AccountId> {
+            let seed =
+                crate::random::<crate::DefaultEnvironment>(&round.to_le_bytes())?;
+            let index = seed.as_ref()[0] as usize % participants.len();
+            Ok(participants[index])
+        };
+        crate::test::set_block_entropy::<crate::DefaultEnvironment>(Hash::from(
+            [0x01; 32],
+        ))?;
+        // The same subject yields the same random hash within an execution.
+        assert_eq!(
+            crate::random::<crate::DefaultEnvironment>(b"raffle"),
+            crate::random::<crate::DefaultEnvironment>(b"raffle"),
+        );
+        assert_ne!(
+            crate::random::<crate::DefaultEnvironment>(b"raffle"),
+            crate::random::<crate::DefaultEnvironment>(b"lottery"),
+        );
+        // With a fixed entropy the winners are deterministic.
+        assert_eq!(pick_winner(1)?, default_accounts.alice);
+        assert_eq!(pick_winner(2)?, default_accounts.django);
+        assert_eq!(pick_winner(1)?, default_accounts.alice);
+        // Different entropy selects a different winner.
+        crate::test::set_block_entropy::<crate::DefaultEnvironment>(Hash::from(
+            [0x03; 32],
+        ))?;
+        assert_eq!(pick_winner(1)?, default_accounts.charlie);
+        Ok(())
+    })
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use core::{
+    cmp::Ordering,
+    convert::TryFrom,
     fmt,
     ops::{
         Add,
@@ -29,21 +31,46 @@ use core::{
 ///
 /// This is the most low-level primitive to identify contract storage cells.
 ///
+/// The SCALE encoding of a key consists of its plain 32 bytes without any
+/// length prefix. This encoding is stable so keys can safely be persisted,
+/// e.g. as values within the contract storage.
+///
 /// # Unsafe
 ///
 /// Prefer using high-level types found in `ink_storage` to operate on the contract
 /// storage.
-#[derive(Copy, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Copy, Default, Clone, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct Key([u64; 4]);
 
+impl PartialOrd for Key {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    /// Compares keys by their numeric value.
+    ///
+    /// # Note
+    ///
+    /// This is consistent with the key arithmetic, so `key < key + 1` holds
+    /// for all keys that do not wrap around the 256-bit key space.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The limbs are stored from least to most significant.
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
 impl Key {
     fn write_bytes(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "0x")?;
         for limb in &self.0 {
             write!(f, "_")?;
             for byte in &limb.to_le_bytes() {
-                write!(f, "{:02X}", byte)?;
+                write!(f, "{:02x}", byte)?;
             }
         }
         Ok(())
@@ -85,7 +112,108 @@ impl From<[u8; 32]> for Key {
     }
 }
 
+/// Error returned when creating a key from a slice that is not 32 bytes long.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InvalidKeyLength {
+    /// The length of the rejected slice.
+    pub len: usize,
+}
+
+impl fmt::Display for InvalidKeyLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected 32 bytes for a key but found {} bytes", self.len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidKeyLength {}
+
+impl<'a> TryFrom<&'a [u8]> for Key {
+    type Error = InvalidKeyLength;
+
+    #[inline]
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        <[u8; 32]>::try_from(bytes)
+            .map(Self::from)
+            .map_err(|_| InvalidKeyLength { len: bytes.len() })
+    }
+}
+
 impl Key {
+    /// Creates a new key from the given bytes.
+    ///
+    /// # Note
+    ///
+    /// Unlike the `From<[u8; 32]>` implementation this can be used in `const`
+    /// and `static` items, e.g. to declare storage root keys at compile time.
+    #[inline]
+    pub const fn new(bytes: [u8; 32]) -> Self {
+        Self::from_bytes_be_fallback(bytes)
+    }
+
+    /// Creates a new key from the given byte slice.
+    ///
+    /// # Errors
+    ///
+    /// If the slice is not exactly 32 bytes long.
+    #[inline]
+    pub fn try_from_slice(bytes: &[u8]) -> Result<Self, InvalidKeyLength> {
+        Self::try_from(bytes)
+    }
+
+    /// Creates a new key with the given value in its least significant bytes.
+    ///
+    /// This is the same as `Key::default() + n` but can be used in `const`
+    /// and `static` items.
+    #[inline]
+    pub const fn from_u64(n: u64) -> Self {
+        Self([n, 0, 0, 0])
+    }
+
+    /// Creates a new key from the Keccak-256 hash of the given namespace.
+    ///
+    /// This is the recommended way to manually choose root keys for storage
+    /// entities, e.g. `Key::from_hash(b"my_contract::counter")` or the
+    /// shorthand [`key!`](`crate::key!`), since keys derived from distinct
+    /// namespaces are virtually guaranteed to not collide in the 2^256 cells
+    /// of the contract storage.
+    ///
+    /// # Note
+    ///
+    /// The derivation is stable across builds and target machines.
+    pub fn from_hash(namespace: &[u8]) -> Self {
+        Self::from_hash_of(&[namespace])
+    }
+
+    /// Derives the key of a child storage region from `self`.
+    ///
+    /// The child key is the Keccak-256 hash of the parent key bytes followed by
+    /// the `discriminant`. This allows nested storage entities to each claim an
+    /// independent region of the key space, e.g. one per struct field, instead
+    /// of manually partitioning the region that follows the parent key.
+    ///
+    /// # Note
+    ///
+    /// The derivation is stable across builds and target machines.
+    pub fn child(&self, discriminant: &[u8]) -> Self {
+        Self::from_hash_of(&[&self.to_bytes(), discriminant])
+    }
+
+    /// Creates a new key from the Keccak-256 hash of the concatenated parts.
+    fn from_hash_of(parts: &[&[u8]]) -> Self {
+        use tiny_keccak::{
+            Hasher,
+            Keccak,
+        };
+        let mut output = [0x00; 32];
+        let mut hasher = Keccak::v256();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize(&mut output);
+        Self::from(output)
+    }
+
     /// Creates a new key from the given bytes.
     ///
     /// # Note
@@ -93,9 +221,9 @@ impl Key {
     /// This is a fallback procedure in case the target machine does not have
     /// little endian byte ordering.
     #[inline]
-    fn from_bytes_be_fallback(bytes: [u8; 32]) -> Self {
+    const fn from_bytes_be_fallback(bytes: [u8; 32]) -> Self {
         #[inline]
-        fn carve_out_u64_bytes(bytes: &[u8; 32], offset: u8) -> [u8; 8] {
+        const fn carve_out_u64_bytes(bytes: &[u8; 32], offset: u8) -> [u8; 8] {
             let o = (offset * 8) as usize;
             [
                 bytes[o],
@@ -246,19 +374,80 @@ const _: () = {
 
 impl AddAssign<u64> for Key {
     #[inline]
-    #[rustfmt::skip]
     fn add_assign(&mut self, rhs: u64) {
-        let (res_0,  ovfl_0) = self.0[0].overflowing_add(rhs);
-        let (res_1,  ovfl_1) = self.0[1].overflowing_add(ovfl_0 as u64);
-        let (res_2,  ovfl_2) = self.0[2].overflowing_add(ovfl_1 as u64);
-        let (res_3, _ovfl_3) = self.0[3].overflowing_add(ovfl_2 as u64);
-        self.0[0] = res_0;
-        self.0[1] = res_1;
-        self.0[2] = res_2;
-        self.0[3] = res_3;
+        *self = self.overflowing_add(rhs).0;
     }
 }
 
+impl Key {
+    /// Calculates `self + rhs`.
+    ///
+    /// Returns a tuple of the resulting key along with a boolean indicating
+    /// whether the addition wrapped around the 256-bit key space.
+    #[inline]
+    #[rustfmt::skip]
+    pub fn overflowing_add(self, rhs: u64) -> (Self, bool) {
+        let (res_0, ovfl_0) = self.0[0].overflowing_add(rhs);
+        let (res_1, ovfl_1) = self.0[1].overflowing_add(ovfl_0 as u64);
+        let (res_2, ovfl_2) = self.0[2].overflowing_add(ovfl_1 as u64);
+        let (res_3, ovfl_3) = self.0[3].overflowing_add(ovfl_2 as u64);
+        (Self([res_0, res_1, res_2, res_3]), ovfl_3)
+    }
+
+    /// Calculates `self + rhs`.
+    ///
+    /// Returns `None` if the addition wrapped around the 256-bit key space.
+    #[inline]
+    pub fn checked_add(self, rhs: u64) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (result, false) => Some(result),
+            (_, true) => None,
+        }
+    }
+
+    /// Returns the distance from `origin` to `self` in number of cells.
+    ///
+    /// This is the inverse of adding an offset to a key, so for any key `k`
+    /// and offset `n` that does not wrap `(k + n).offset_from(&k) == Some(n)`.
+    ///
+    /// Returns `None` if `origin` is greater than `self` or if the distance
+    /// does not fit into a `u64`.
+    pub fn offset_from(&self, origin: &Key) -> Option<u64> {
+        let mut diff = [0x00_u64; 4];
+        let mut borrow = false;
+        for (n, limb) in diff.iter_mut().enumerate() {
+            let (res, borrow_0) = self.0[n].overflowing_sub(origin.0[n]);
+            let (res, borrow_1) = res.overflowing_sub(borrow as u64);
+            *limb = res;
+            borrow = borrow_0 || borrow_1;
+        }
+        if borrow || diff[1..].iter().any(|&limb| limb != 0) {
+            return None
+        }
+        Some(diff[0])
+    }
+}
+
+/// Creates a new key from the Keccak-256 hash of the given namespace string.
+///
+/// This is a shorthand for [`Key::from_hash`] and the recommended way to
+/// manually choose root keys for storage entities from human readable names.
+///
+/// # Example
+///
+/// ```
+/// # use ink_primitives::{key, Key};
+/// let counter = key!("my_contract::counter");
+/// assert_eq!(counter, Key::from_hash(b"my_contract::counter"));
+/// assert_ne!(counter, key!("my_contract::owner"));
+/// ```
+#[macro_export]
+macro_rules! key {
+    ( $namespace:expr ) => {
+        $crate::Key::from_hash(::core::primitive::str::as_bytes($namespace))
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,14 +474,30 @@ mod tests {
             String::from(
                 "Key(0x\
                     _0001020304050607\
-                    _08090A0B0C0D0E0F\
+                    _08090a0b0c0d0e0f\
                     _1011121314151617\
-                    _18191A1B1C1D1E1F\
+                    _18191a1b1c1d1e1f\
                 )"
             ),
         );
     }
 
+    #[test]
+    fn display_works() {
+        let key = Key::from(test_bytes());
+        assert_eq!(
+            format!("{}", key),
+            String::from(
+                "0x\
+                    _0001020304050607\
+                    _08090a0b0c0d0e0f\
+                    _1011121314151617\
+                    _18191a1b1c1d1e1f\
+                "
+            ),
+        );
+    }
+
     #[test]
     #[rustfmt::skip]
     fn from_works() {
@@ -303,6 +508,110 @@ mod tests {
         assert_eq!(Key::from_bytes_be_fallback(test_bytes).to_bytes_be_fallback(), test_bytes);
     }
 
+    #[test]
+    fn from_hash_works() {
+        assert_eq!(
+            Key::from_hash(b"").to_bytes(),
+            *b"\
+                \xC5\xD2\x46\x01\x86\xF7\x23\x3C\
+                \x92\x7E\x7D\xB2\xDC\xC7\x03\xC0\
+                \xE5\x00\xB6\x53\xCA\x82\x27\x3B\
+                \x7B\xFA\xD8\x04\x5D\x85\xA4\x70\
+            ",
+        );
+        assert_eq!(
+            Key::from_hash(b"abc").to_bytes(),
+            *b"\
+                \x4E\x03\x65\x7A\xEA\x45\xA9\x4F\
+                \xC7\xD4\x7B\xA8\x26\xC8\xD6\x67\
+                \xC0\xD1\xE6\xE3\x3A\x64\xA0\x36\
+                \xEC\x44\xF5\x8F\xA1\x2D\x6C\x45\
+            ",
+        );
+        assert_ne!(Key::from_hash(b"field_a"), Key::from_hash(b"field_b"));
+    }
+
+    #[test]
+    fn key_macro_works() {
+        assert_eq!(crate::key!(""), Key::from_hash(b""));
+        assert_eq!(crate::key!("abc"), Key::from_hash(b"abc"));
+        let namespace = "my_contract::counter";
+        assert_eq!(crate::key!(namespace), Key::from_hash(namespace.as_bytes()));
+    }
+
+    #[test]
+    fn encode_decode_works() {
+        let key = Key::from(test_bytes());
+        let encoded = scale::Encode::encode(&key);
+        // The encoding is the plain 32 bytes of the key without length prefix.
+        assert_eq!(encoded, test_bytes().to_vec());
+        let decoded = <Key as scale::Decode>::decode(&mut &encoded[..]);
+        assert_eq!(decoded, Ok(key));
+        // Decoding fails for too short inputs.
+        assert!(<Key as scale::Decode>::decode(&mut &encoded[..31]).is_err());
+    }
+
+    #[test]
+    fn try_from_slice_works() {
+        let bytes = test_bytes();
+        assert_eq!(Key::try_from(&bytes[..]), Ok(Key::from(bytes)));
+        assert_eq!(Key::try_from_slice(&bytes[..]), Ok(Key::from(bytes)));
+        for len in &[0, 1, 31, 33, 64] {
+            let bytes = vec![0x01; *len];
+            assert_eq!(
+                Key::try_from(&bytes[..]),
+                Err(InvalidKeyLength { len: *len })
+            );
+            assert_eq!(
+                Key::try_from_slice(&bytes[..]),
+                Err(InvalidKeyLength { len: *len })
+            );
+        }
+        assert_eq!(
+            format!("{}", InvalidKeyLength { len: 31 }),
+            "expected 32 bytes for a key but found 31 bytes",
+        );
+    }
+
+    #[test]
+    fn invalid_key_length_works_with_question_mark() {
+        fn parse(bytes: &[u8]) -> Result<Key, Box<dyn std::error::Error>> {
+            Ok(Key::try_from_slice(bytes)?)
+        }
+        assert_eq!(parse(&test_bytes()).ok(), Some(Key::from(test_bytes())));
+        assert_eq!(
+            parse(&[0x01; 31]).map_err(|error| error.to_string()),
+            Err(String::from("expected 32 bytes for a key but found 31 bytes")),
+        );
+    }
+
+    #[test]
+    fn child_works() {
+        let parent = Key::from(test_bytes());
+        let child_a = parent.child(b"a");
+        let child_b = parent.child(b"b");
+        assert_ne!(child_a, child_b);
+        assert_ne!(child_a, parent);
+        // Derivation is deterministic:
+        assert_eq!(parent.child(b"a"), child_a);
+        // Children of different parents differ:
+        assert_ne!(Key::default().child(b"a"), child_a);
+        // A child is the hash of the parent bytes followed by the discriminant:
+        let mut preimage = test_bytes().to_vec();
+        preimage.extend_from_slice(b"a");
+        assert_eq!(child_a, Key::from_hash(&preimage));
+    }
+
+    #[test]
+    fn const_constructors_work() {
+        const ROOT_KEY: Key = Key::new([0x01; 32]);
+        static COUNTER_KEY: Key = Key::from_u64(42);
+        assert_eq!(ROOT_KEY, Key::from([0x01; 32]));
+        assert_eq!(Key::new(test_bytes()), Key::from(test_bytes()));
+        assert_eq!(COUNTER_KEY, Key::default() + 42);
+        assert_eq!(Key::from_u64(u64::MAX), Key::default() + u64::MAX);
+    }
+
     #[test]
     fn add_one_to_zero() {
         let bytes = [0x00; 32];
@@ -379,6 +688,113 @@ mod tests {
         assert_eq!(key.to_bytes(), expected);
     }
 
+    #[test]
+    fn overflowing_add_works() {
+        // No overflow at all:
+        let key = Key::from([0x00; 32]);
+        let (result, overflow) = key.overflowing_add(42);
+        assert_eq!(result, key + 42);
+        assert!(!overflow);
+        // Carry that stops after the first limb:
+        let mut bytes = [0x00; 32];
+        bytes[0..8].copy_from_slice(&[0xFF; 8]);
+        let (result, overflow) = Key::from(bytes).overflowing_add(1);
+        let expected = {
+            let mut expected = [0x00; 32];
+            expected[8] = 0x01;
+            expected
+        };
+        assert_eq!(result.to_bytes(), expected);
+        assert!(!overflow);
+        // Carry that escapes the most significant limb:
+        let (result, overflow) = Key::from([0xFF; 32]).overflowing_add(1);
+        assert_eq!(result.to_bytes(), [0x00; 32]);
+        assert!(overflow);
+    }
+
+    #[test]
+    fn add_zero_is_identity() {
+        let key = Key::from(test_bytes());
+        assert_eq!(key + 0, key);
+        assert_eq!(&key + 0, key);
+        assert_eq!(Key::from([0xFF; 32]) + 0, Key::from([0xFF; 32]));
+    }
+
+    #[test]
+    fn successive_add_one_equals_bulk_add() {
+        let mut bytes = [0x00; 32];
+        bytes[0..8].copy_from_slice(&(u64::MAX - 500).to_le_bytes());
+        let key = Key::from(bytes);
+        let mut successive = key;
+        for n in 1..=1000_u64 {
+            successive += 1;
+            assert_eq!(successive, key + n);
+        }
+    }
+
+    #[test]
+    fn checked_add_works() {
+        let key = Key::from(test_bytes());
+        assert_eq!(key.checked_add(0), Some(key));
+        assert_eq!(key.checked_add(u64::MAX), Some(key + u64::MAX));
+        let max = Key::from([0xFF; 32]);
+        assert_eq!(max.checked_add(0), Some(max));
+        assert_eq!(max.checked_add(1), None);
+        let near_max = {
+            let mut bytes = [0xFF; 32];
+            bytes[0..8].copy_from_slice(&41_u64.to_le_bytes());
+            Key::from(bytes)
+        };
+        assert_eq!(near_max.checked_add(u64::MAX - 41), Some(max));
+        assert_eq!(near_max.checked_add(u64::MAX - 40), None);
+    }
+
+    #[test]
+    fn offset_from_works() {
+        let key = Key::from(test_bytes());
+        assert_eq!(key.offset_from(&key), Some(0));
+        for offset in &[1_u64, 42, u32::MAX as u64, u64::MAX] {
+            assert_eq!((key + *offset).offset_from(&key), Some(*offset));
+        }
+        // Distance that requires a borrow across the first limb:
+        let mut bytes = [0x00; 32];
+        bytes[8] = 0x01;
+        let above = Key::from(bytes);
+        let below = Key::from({
+            let mut bytes = [0x00; 32];
+            bytes[0..8].copy_from_slice(&[0xFF; 8]);
+            bytes
+        });
+        assert_eq!(above.offset_from(&below), Some(1));
+        assert_eq!(below.offset_from(&above), None);
+        // Distance of exactly `u64::MAX + 1` does not fit:
+        assert_eq!(above.offset_from(&Key::default()), None);
+        assert_eq!(below.offset_from(&Key::default()), Some(u64::MAX));
+    }
+
+    #[test]
+    fn ord_works() {
+        let zero = Key::default();
+        let max = Key::from([0xFF; 32]);
+        assert!(zero < max);
+        assert_eq!(zero.cmp(&zero), Ordering::Equal);
+        // Keys right below each limb carry boundary:
+        for limb in 0..3 {
+            let mut bytes = [0x00; 32];
+            bytes[0..(limb + 1) * 8].copy_from_slice(&[0xFF; 32][0..(limb + 1) * 8]);
+            let key = Key::from(bytes);
+            assert!(key < key + 1);
+            assert!(key + 1 > key);
+            assert!(key < max);
+        }
+        // The most significant byte dominates the least significant one:
+        let mut low = [0x00; 32];
+        low[0] = 0xFF;
+        let mut high = [0x00; 32];
+        high[31] = 0x01;
+        assert!(Key::from(low) < Key::from(high));
+    }
+
     #[test]
     fn add_assign_to_zero() {
         for test_value in &[0_u64, 1, 42, 10_000, u32::MAX as u64, u64::MAX] {
@@ -35,10 +35,56 @@ impl From<Key> for KeyPtr {
 
 impl KeyPtr {
     /// Advances the key pointer by the given amount and returns the old value.
+    ///
+    /// # Panics
+    ///
+    /// If advancing the key pointer wraps around the 256-bit key space.
     #[inline]
     pub fn advance_by(&mut self, new_shift: u64) -> &Key {
         let old_shift = core::mem::replace(&mut self.last_shift, new_shift);
-        self.key += old_shift;
+        let (new_key, overflow) = self.key.overflowing_add(old_shift);
+        assert!(
+            !overflow,
+            "encountered key overflow while advancing key pointer by {}",
+            old_shift,
+        );
+        self.key = new_key;
         &self.key
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_by_works() {
+        let mut key_ptr = KeyPtr::from(Key::from([0x00; 32]));
+        assert_eq!(key_ptr.advance_by(5), &Key::from([0x00; 32]));
+        assert_eq!(key_ptr.advance_by(1), &(Key::from([0x00; 32]) + 5));
+        assert_eq!(key_ptr.advance_by(0), &(Key::from([0x00; 32]) + 6));
+    }
+
+    #[test]
+    fn rewind_by_copy_works() {
+        let mut key_ptr = KeyPtr::from(Key::from([0x00; 32]));
+        let _ = key_ptr.advance_by(3);
+        // A copy of the key pointer serves as a checkpoint of the layout.
+        let checkpoint = key_ptr;
+        let first_layout = [*key_ptr.advance_by(2), *key_ptr.advance_by(4)];
+        // Rewinding to the checkpoint and retrying yields the same layout.
+        key_ptr = checkpoint;
+        let second_layout = [*key_ptr.advance_by(2), *key_ptr.advance_by(4)];
+        assert_eq!(first_layout, second_layout);
+        assert_eq!(first_layout[0], Key::from([0x00; 32]) + 3);
+        assert_eq!(first_layout[1], Key::from([0x00; 32]) + 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "encountered key overflow while advancing key pointer")]
+    fn advance_by_overflow_panics() {
+        let mut key_ptr = KeyPtr::from(Key::from([0xFF; 32]));
+        let _ = key_ptr.advance_by(1);
+        let _ = key_ptr.advance_by(1);
+    }
+}
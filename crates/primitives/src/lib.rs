@@ -27,6 +27,9 @@ mod key;
 mod key_ptr;
 
 pub use self::{
-    key::Key,
+    key::{
+        InvalidKeyLength,
+        Key,
+    },
     key_ptr::KeyPtr,
 };
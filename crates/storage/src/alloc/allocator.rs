@@ -29,6 +29,30 @@ pub struct DynamicAllocator {
     allocations: BitStash,
 }
 
+/// Statistics about the dynamic storage allocations of a contract.
+///
+/// # Note
+///
+/// The counters are not stored in storage cells of their own. Instead they are
+/// derived from the bit stash of the dynamic storage allocator that is persisted
+/// anyways. This keeps them accurate across contract executions without changing
+/// the storage layout of the allocator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AllocatorStats {
+    /// The number of currently live dynamic allocations.
+    ///
+    /// Every dynamic allocation refers to its own storage cell.
+    pub allocated: u32,
+    /// The number of freed dynamic allocations that are available for reuse
+    /// before new allocations are handed out.
+    pub free: u32,
+    /// One past the highest dynamic allocation index ever handed out.
+    ///
+    /// Since freed allocations are reused first this is also the peak number
+    /// of dynamic allocations that have been live at the same time.
+    pub high_water: u32,
+}
+
 #[cfg(feature = "std")]
 const _: () = {
     use crate::traits::StorageLayout;
@@ -99,4 +123,15 @@ impl DynamicAllocator {
             )
         }
     }
+
+    /// Returns statistics about the current dynamic storage allocations.
+    pub fn stats(&self) -> AllocatorStats {
+        let allocated = self.allocations.count_ones();
+        let high_water = self.allocations.capacity();
+        AllocatorStats {
+            allocated,
+            free: high_water - allocated,
+            high_water,
+        }
+    }
 }
@@ -16,6 +16,7 @@ use super::Box as StorageBox;
 use crate::{
     alloc,
     alloc::ContractPhase,
+    collections::Vec as StorageVec,
     traits::{
         KeyPtr,
         SpreadLayout,
@@ -196,6 +197,28 @@ fn recursive_clear_works() {
     })
 }
 
+#[test]
+fn nested_collections_free_inner_allocations() {
+    run_test(|_| {
+        let mut outer = (0..3)
+            .map(|n| StorageBox::new((0..n).collect::<StorageVec<u8>>()))
+            .collect::<StorageVec<_>>();
+        assert!(outer.iter().map(|inner| inner.len()).eq(0..3));
+        assert_eq!(alloc::stats().allocated, 3);
+        // Dropping an inner collection frees its dynamic allocation.
+        let last = outer.pop().expect("encountered empty outer vector");
+        assert!(last.iter().eq([0, 1].iter()));
+        drop(last);
+        assert_eq!(alloc::stats().allocated, 2);
+        assert_eq!(alloc::stats().free, 1);
+        // The freed dynamic allocation is reused by the next inner collection.
+        outer.push(StorageBox::new([b'A'].iter().copied().collect()));
+        assert_eq!(alloc::stats().allocated, 3);
+        assert_eq!(alloc::stats().free, 0);
+        assert!(outer[2].iter().eq([b'A'].iter()));
+    })
+}
+
 #[test]
 #[should_panic(expected = "encountered double free of dynamic storage: at index 0")]
 fn double_free_panics() {
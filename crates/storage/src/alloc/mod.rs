@@ -77,6 +77,7 @@ mod init;
 mod tests;
 
 use self::allocator::DynamicAllocator;
+pub use self::allocator::AllocatorStats;
 pub use self::{
     allocation::DynamicAllocation,
     boxed::Box,
@@ -96,6 +97,14 @@ pub fn free(allocation: DynamicAllocation) {
     init::on_instance(|allocator| allocator.free(allocation))
 }
 
+/// Returns statistics about the dynamic storage allocations of the contract.
+///
+/// The statistics are derived from the persisted state of the dynamic storage
+/// allocator and are therefore accurate across contract executions.
+pub fn stats() -> AllocatorStats {
+    init::on_instance(|allocator| allocator.stats())
+}
+
 /// Tells the global dynamic storage allocator instance how it shall initialize.
 ///
 /// # Note
@@ -15,6 +15,8 @@
 use super::{
     alloc,
     free,
+    stats,
+    AllocatorStats,
     ContractPhase,
     DynamicAllocation,
     DynamicAllocator,
@@ -124,6 +126,70 @@ fn free_out_of_bounds() {
     })
 }
 
+#[test]
+fn interleaved_alloc_and_free_never_overlap() {
+    run_default_test(|| {
+        let mut live = std::collections::BTreeSet::new();
+        let mut seed = 0x2545_f491_u32;
+        for _ in 0..TEST_ALLOCATIONS {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            if seed % 3 == 0 && !live.is_empty() {
+                // Free a pseudo-randomly chosen live allocation.
+                let nth = (seed >> 8) as usize % live.len();
+                let index = *live.iter().nth(nth).unwrap();
+                live.remove(&index);
+                free(DynamicAllocation(index));
+            } else {
+                // New allocations always fill the lowest free slot.
+                let expected = (0..).find(|index| !live.contains(index)).unwrap();
+                let allocation = alloc();
+                assert_eq!(allocation, DynamicAllocation(expected));
+                assert!(live.insert(allocation.get()));
+            }
+        }
+    })
+}
+
+#[test]
+fn stats_works() {
+    run_default_test(|| {
+        assert_eq!(
+            stats(),
+            AllocatorStats {
+                allocated: 0,
+                free: 0,
+                high_water: 0,
+            }
+        );
+        for i in 0..10 {
+            assert_eq!(alloc(), DynamicAllocation(i));
+        }
+        free(DynamicAllocation(2));
+        free(DynamicAllocation(5));
+        free(DynamicAllocation(9));
+        assert_eq!(
+            stats(),
+            AllocatorStats {
+                allocated: 7,
+                free: 3,
+                high_water: 10,
+            }
+        );
+        // Reusing a freed allocation does not raise the high-water mark.
+        assert_eq!(alloc(), DynamicAllocation(2));
+        assert_eq!(
+            stats(),
+            AllocatorStats {
+                allocated: 8,
+                free: 2,
+                high_water: 10,
+            }
+        );
+    })
+}
+
 fn spread_layout_alloc_setup() -> DynamicAllocator {
     let mut alloc = DynamicAllocator::default();
     assert_eq!(alloc.alloc(), DynamicAllocation(0));
@@ -154,6 +220,7 @@ fn spread_pull_push_works() {
         assert_eq!(alloc2.alloc(), DynamicAllocation(1));
         assert_eq!(alloc2.alloc(), DynamicAllocation(3));
         assert_eq!(alloc2, alloc);
+        assert_eq!(alloc2.stats(), alloc.stats());
     })
 }
 
@@ -200,6 +267,15 @@ fn test_call_setup_works() {
         assert_eq!(alloc(), DynamicAllocation(3));
         free(DynamicAllocation(0));
         free(DynamicAllocation(2));
+        // The statistics have been carried over from the pushed allocator.
+        assert_eq!(
+            stats(),
+            AllocatorStats {
+                allocated: 2,
+                free: 2,
+                high_water: 4,
+            }
+        );
         Ok(())
     })
     .unwrap();
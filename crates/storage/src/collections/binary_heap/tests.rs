@@ -427,3 +427,36 @@ fn fuzz_pop_always_returns_largest_element(xs: Vec<i32>) {
     })
     .unwrap()
 }
+
+#[cfg(feature = "ink-fuzz-tests")]
+#[quickcheck]
+fn fuzz_push_pop_matches_std_binary_heap_across_push_pull(ops: Vec<Option<i32>>) {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let mut heap = BinaryHeap::new();
+        let mut expected = std::collections::BinaryHeap::new();
+        for (n, op) in ops.into_iter().enumerate() {
+            match op {
+                Some(x) => {
+                    heap.push(x);
+                    expected.push(x);
+                }
+                None => assert_eq!(heap.pop(), expected.pop()),
+            }
+            assert_eq!(heap.peek(), expected.peek());
+            if n % 8 == 0 {
+                // The heap property must still hold after reloading the heap.
+                SpreadLayout::push_spread(&heap, &mut KeyPtr::from(root_key));
+                core::mem::forget(heap);
+                heap = SpreadLayout::pull_spread(&mut KeyPtr::from(root_key));
+            }
+        }
+        assert_eq!(heap.len() as usize, expected.len());
+        while let Some(x) = expected.pop() {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert_eq!(heap.pop(), None);
+        Ok(())
+    })
+    .unwrap()
+}
@@ -97,6 +97,19 @@ impl BitStash {
         1 + ((capacity - 1) / (32 * 256)) as u32
     }
 
+    /// Returns the number of slots of the storage bit stash.
+    ///
+    /// This is the number of slots that have been put at least once,
+    /// regardless of whether they have been taken again afterwards.
+    pub fn capacity(&self) -> u32 {
+        self.free.len()
+    }
+
+    /// Returns the number of set bits in the storage bit stash.
+    pub fn count_ones(&self) -> u32 {
+        self.free.count_ones()
+    }
+
     /// Returns `true` if the bit at the indexed slot is set (`1`).
     ///
     /// Returns `None` if the index is out of bounds.
@@ -18,6 +18,7 @@ use super::{
     Bits256,
     Index256,
 };
+use core::cell::Cell;
 
 /// A mutable bit access for operating on a single bit within a 256-bit pack.
 #[derive(Debug)]
@@ -26,6 +27,8 @@ pub struct BitRefMut<'a> {
     bits: &'a mut Bits256,
     /// The bit position witihn the queried bit pack.
     at: u8,
+    /// The number of set bits of the storage bit vector the bit belongs to.
+    ones: &'a Cell<u32>,
 }
 
 impl<'a> PartialEq for BitRefMut<'a> {
@@ -38,8 +41,25 @@ impl<'a> Eq for BitRefMut<'a> {}
 
 impl<'a> BitRefMut<'a> {
     /// Creates a new bit access for the indexed bit within the 256-bit pack.
-    pub(super) fn new(bits: &'a mut Bits256, at: Index256) -> Self {
-        Self { bits, at }
+    ///
+    /// Mutations of the bit update `ones` accordingly.
+    pub(super) fn new(
+        bits: &'a mut Bits256,
+        at: Index256,
+        ones: &'a Cell<u32>,
+    ) -> Self {
+        Self { bits, at, ones }
+    }
+
+    /// Applies `f` to the indexed bit and updates the number of set bits.
+    fn update<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Bits256, Index256),
+    {
+        let old_value = self.get();
+        f(self.bits, self.at);
+        let new_value = self.get();
+        self.ones.set(self.ones.get() + new_value as u32 - old_value as u32);
     }
 
     /// Returns the value of the indexed bit.
@@ -54,37 +74,37 @@ impl<'a> BitRefMut<'a> {
 
     /// Sets the value of the indexed bit to the given new value.
     pub fn set_to(&mut self, new_value: bool) {
-        self.bits.set_to(self.at, new_value)
+        self.update(|bits, at| bits.set_to(at, new_value))
     }
 
     /// Sets the indexed bit to `1` (true).
     pub fn set(&mut self) {
-        self.bits.set(self.at)
+        self.update(|bits, at| bits.set(at))
     }
 
     /// Resets the indexed bit to `0` (false).
     pub fn reset(&mut self) {
-        self.bits.reset(self.at)
+        self.update(|bits, at| bits.reset(at))
     }
 
     /// Flips the indexed bit.
     pub fn flip(&mut self) {
-        self.bits.flip(self.at)
+        self.update(|bits, at| bits.flip(at))
     }
 
     /// Computes bitwise XOR for the indexed bit and `rhs`.
     pub fn xor(&mut self, rhs: bool) {
-        self.bits.xor(self.at, rhs)
+        self.update(|bits, at| bits.xor(at, rhs))
     }
 
     /// Computes bitwise AND for the indexed bit and `rhs`.
     pub fn and(&mut self, rhs: bool) {
-        self.bits.and(self.at, rhs)
+        self.update(|bits, at| bits.and(at, rhs))
     }
 
     /// Computes bitwise OR for the indexed bit and `rhs`.
     pub fn or(&mut self, rhs: bool) {
-        self.bits.or(self.at, rhs)
+        self.update(|bits, at| bits.or(at, rhs))
     }
 }
 
@@ -92,6 +112,7 @@ impl<'a> BitRefMut<'a> {
 mod tests {
     use super::BitRefMut;
     use crate::collections::bitvec::Bits256;
+    use core::cell::Cell;
 
     fn is_populated_bit_set(index: u8) -> bool {
         (index % 5) == 0 || (index % 13) == 0
@@ -109,8 +130,9 @@ mod tests {
     #[test]
     fn get_set_works() {
         let mut bits256 = populated_bits256();
+        let ones = Cell::new(bits256.count_ones());
         for i in 0..=255 {
-            let mut bitref = BitRefMut::new(&mut bits256, i);
+            let mut bitref = BitRefMut::new(&mut bits256, i, &ones);
             let expected = is_populated_bit_set(i);
             assert_eq!(bitref.get(), expected);
             // Set only every second bit to true and check this later:
@@ -120,26 +142,32 @@ mod tests {
         for i in 0..=255 {
             assert_eq!(bits256.get(i), i % 2 == 0);
         }
+        // The number of set bits has been kept up to date:
+        assert_eq!(ones.get(), bits256.count_ones());
     }
 
     #[test]
     fn flip_works() {
         let mut bits256 = populated_bits256();
+        let ones = Cell::new(bits256.count_ones());
         for i in 0..=255 {
-            let mut bitref = BitRefMut::new(&mut bits256, i);
+            let mut bitref = BitRefMut::new(&mut bits256, i, &ones);
             bitref.flip();
         }
         // Check if `flip` was successful:
         for i in 0..=255 {
             assert_eq!(bits256.get(i), !is_populated_bit_set(i));
         }
+        // The number of set bits has been kept up to date:
+        assert_eq!(ones.get(), bits256.count_ones());
     }
 
     #[test]
     fn set_and_reset_works() {
         let mut bits256 = populated_bits256();
+        let ones = Cell::new(bits256.count_ones());
         for i in 0..=255 {
-            let mut bitref = BitRefMut::new(&mut bits256, i);
+            let mut bitref = BitRefMut::new(&mut bits256, i, &ones);
             if i % 2 == 0 {
                 bitref.set();
             } else {
@@ -150,13 +178,16 @@ mod tests {
         for i in 0..=255 {
             assert_eq!(bits256.get(i), i % 2 == 0);
         }
+        // The number of set bits has been kept up to date:
+        assert_eq!(ones.get(), bits256.count_ones());
     }
 
     #[test]
     fn bitops_works() {
         let mut bits256 = populated_bits256();
+        let ones = Cell::new(bits256.count_ones());
         for i in 0..=255 {
-            let mut bitref = BitRefMut::new(&mut bits256, i);
+            let mut bitref = BitRefMut::new(&mut bits256, i, &ones);
             let expected = is_populated_bit_set(i);
             fn test_xor(bitref: &mut BitRefMut, expected: bool) {
                 fn test_xor_for(bitref: &mut BitRefMut, expected: bool, input: bool) {
@@ -192,5 +223,7 @@ mod tests {
             }
             test_or(&mut bitref, expected);
         }
+        // The number of set bits has been kept up to date:
+        assert_eq!(ones.get(), bits256.count_ones());
     }
 }
@@ -19,6 +19,7 @@ use super::{
     Index256,
     Index64,
 };
+use core::cell::Cell;
 
 /// A chunk of 256 bits.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -109,14 +110,17 @@ pub struct IterMut<'a> {
     bits: &'a mut Bits256,
     start: u16,
     end: u16,
+    /// The number of set bits of the storage bit vector the bits belong to.
+    ones: &'a Cell<u32>,
 }
 
 impl<'a> IterMut<'a> {
-    fn new(bits256: &'a mut Bits256, len: u16) -> Self {
+    fn new(bits256: &'a mut Bits256, len: u16, ones: &'a Cell<u32>) -> Self {
         Self {
             bits: bits256,
             start: 0,
             end: len,
+            ones,
         }
     }
 
@@ -126,7 +130,7 @@ impl<'a> IterMut<'a> {
 
     /// Returns a bit access for the given index with extended but valid lifetimes.
     fn get<'b>(&'b mut self, index: u8) -> BitRefMut<'a> {
-        unsafe { BitRefMut::new(extend_lifetime(&mut self.bits), index) }
+        unsafe { BitRefMut::new(extend_lifetime(&mut self.bits), index, self.ones) }
     }
 }
 
@@ -191,8 +195,14 @@ impl Bits256 {
     }
 
     /// Yields mutable accessors to the first `len` bits of the pack of 256 bits.
-    pub(super) fn iter_mut(&mut self, len: u16) -> IterMut {
-        IterMut::new(self, len)
+    ///
+    /// Mutations through the accessors update `ones` accordingly.
+    pub(super) fn iter_mut<'a>(
+        &'a mut self,
+        len: u16,
+        ones: &'a Cell<u32>,
+    ) -> IterMut<'a> {
+        IterMut::new(self, len, ones)
     }
 
     /// Returns the bit value for the bit at the given index.
@@ -249,6 +259,75 @@ impl Bits256 {
         self.op_at_with(at, rhs, |bits64, rhs| *bits64 ^= rhs)
     }
 
+    /// Sets the bit values for all bits within `start..end` to the given value.
+    ///
+    /// # Note
+    ///
+    /// Requires `start <= end <= 256`.
+    pub(super) fn set_range_to(&mut self, start: u16, end: u16, new_value: bool) {
+        debug_assert!(start <= end && end <= 256);
+        let mut offset = 0;
+        for bits64 in &mut self.bits {
+            let lo = start.clamp(offset, offset + 64) - offset;
+            let hi = end.clamp(offset, offset + 64) - offset;
+            offset += 64;
+            if lo == hi {
+                continue
+            }
+            // Bit positions are counted from the most significant bit.
+            let mask = (!0_u64 >> lo) & !(!0_u64).checked_shr(hi as u32).unwrap_or(0);
+            if new_value {
+                *bits64 |= mask
+            } else {
+                *bits64 &= !mask
+            }
+        }
+    }
+
+    /// Returns the number of bits set to `1` (`true`).
+    pub(super) fn count_ones(&self) -> u32 {
+        self.bits.iter().map(|bits64| bits64.count_ones()).sum()
+    }
+
+    /// Returns the result of combining `self` and `other` word-wise using `op`.
+    pub(super) fn zip_with<F>(&self, other: &Self, op: F) -> Self
+    where
+        F: Fn(Bits64, Bits64) -> Bits64,
+    {
+        let mut result = *self;
+        for (lhs, rhs) in result.bits.iter_mut().zip(&other.bits) {
+            *lhs = op(*lhs, *rhs);
+        }
+        result
+    }
+
+    /// Returns a pack of 256 bits in which exactly those of the first `len` bits
+    /// are set that are equal to `value` in `self`.
+    pub(super) fn select(&self, value: bool, len: u16) -> Self {
+        let mut selected = *self;
+        if !value {
+            for bits64 in &mut selected.bits {
+                *bits64 = !*bits64;
+            }
+        }
+        selected.set_range_to(len, 256, false);
+        selected
+    }
+
+    /// Resets the first set bit and returns its position if any.
+    pub(super) fn take_first_set(&mut self) -> Option<u8> {
+        let mut offset: u32 = 0;
+        for bits64 in &mut self.bits {
+            if *bits64 != 0 {
+                let pos64 = bits64.leading_zeros();
+                *bits64 &= !(0x01 << (63 - pos64));
+                return Some((pos64 + offset) as u8)
+            }
+            offset += 64;
+        }
+        None
+    }
+
     /// Returns the position of the first zero bit if any.
     pub fn position_first_zero(&self) -> Option<u8> {
         let mut offset: u32 = 0;
@@ -352,6 +431,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn set_range_to_works() {
+        let ranges = [(0, 0), (0, 256), (3, 17), (60, 70), (64, 128), (100, 256)];
+        for &(start, end) in &ranges {
+            for &value in &[true, false] {
+                let mut bits256 = populated_bits256();
+                bits256.set_range_to(start, end, value);
+                for i in 0..256 {
+                    let expected = if (start..end).contains(&i) {
+                        value
+                    } else {
+                        (i % 5) == 0 || (i % 13) == 0
+                    };
+                    assert_eq!(bits256.get(i as u8), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn count_ones_works() {
+        assert_eq!(Bits256::default().count_ones(), 0);
+        assert_eq!(populated_bits256().count_ones(), 68);
+        let mut bits256 = Bits256::default();
+        bits256.set_range_to(3, 200, true);
+        assert_eq!(bits256.count_ones(), 197);
+    }
+
+    #[test]
+    fn select_works() {
+        let bits256 = populated_bits256();
+        for &len in &[0, 1, 64, 100, 256] {
+            for &value in &[true, false] {
+                let selected = bits256.select(value, len);
+                for i in 0..256 {
+                    let expected = i < len && bits256.get(i as u8) == value;
+                    assert_eq!(selected.get(i as u8), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn take_first_set_works() {
+        let mut bits256 = populated_bits256();
+        let mut positions = vec![];
+        while let Some(position) = bits256.take_first_set() {
+            positions.push(position);
+        }
+        assert_eq!(bits256, Bits256::default());
+        let expected = (0..=255_u8)
+            .filter(|i| (i % 5) == 0 || (i % 13) == 0)
+            .collect::<Vec<_>>();
+        assert_eq!(positions, expected);
+    }
+
     #[test]
     fn position_first_zero_works() {
         // Zero bits256:
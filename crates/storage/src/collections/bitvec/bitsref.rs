@@ -20,6 +20,10 @@ use super::{
     Bits256BitsIter,
     Bits256BitsIterMut,
 };
+use core::{
+    cell::Cell,
+    ptr::NonNull,
+};
 
 /// A reference to a subslice within a 256-bit chunk.
 ///
@@ -33,6 +37,14 @@ pub struct ChunkRef<T> {
     bits: T,
     /// The length of the accessible chunk area.
     len: u32,
+    /// The number of set bits of the storage bit vector the chunk belongs to.
+    ///
+    /// # Note
+    ///
+    /// This is only `Some` for exclusive chunk accesses which update it upon
+    /// mutating their bits. It is a pointer instead of a reference since the
+    /// lifetime of the access is only known through `T`.
+    ones: Option<NonNull<Cell<u32>>>,
 }
 
 impl<T> ChunkRef<T> {
@@ -50,7 +62,11 @@ impl<T> ChunkRef<T> {
 impl<'a> ChunkRef<&'a Bits256> {
     /// Creates a new shared 256-bit chunk access with the given length.
     pub(super) fn shared(bits: &'a Bits256, len: u32) -> Self {
-        Self { bits, len }
+        Self {
+            bits,
+            len,
+            ones: None,
+        }
     }
 
     /// Returns the position of the first valid zero bit if any.
@@ -79,12 +95,38 @@ impl<'a> ChunkRef<&'a Bits256> {
     pub(super) fn iter(&self) -> Bits256BitsIter {
         self.bits.iter(self.len as u16)
     }
+
+    /// Returns a copy of the underlying 256 bits in which exactly the valid bits
+    /// equal to `value` are set.
+    pub(super) fn select(&self, value: bool) -> Bits256 {
+        self.bits.select(value, self.len as u16)
+    }
 }
 
 impl<'a> ChunkRef<&'a mut Bits256> {
     /// Creates a new exclusive 256-bit chunk access with the given length.
-    pub(super) fn exclusive(bits: &'a mut Bits256, len: u32) -> Self {
-        Self { bits, len }
+    ///
+    /// Mutations of the bits update `ones` accordingly.
+    pub(super) fn exclusive(
+        bits: &'a mut Bits256,
+        len: u32,
+        ones: &'a Cell<u32>,
+    ) -> Self {
+        Self {
+            bits,
+            len,
+            ones: Some(NonNull::from(ones)),
+        }
+    }
+
+    /// Returns the number of set bits of the storage bit vector.
+    fn ones(&self) -> &'a Cell<u32> {
+        let ones = self
+            .ones
+            .expect("encountered exclusive chunk access without number of set bits");
+        // SAFETY: The pointer has been created from a reference with
+        //         lifetime `'a` upon construction of `self`.
+        unsafe { &*ones.as_ptr() }
     }
 
     /// Returns mutable access to a single bit if the index is out of bounds.
@@ -92,12 +134,14 @@ impl<'a> ChunkRef<&'a mut Bits256> {
         if index as u32 >= self.len {
             return None
         }
-        BitRefMut::new(self.bits, index).into()
+        let ones = self.ones();
+        BitRefMut::new(self.bits, index, ones).into()
     }
 
     /// Returns an iterator over mutable accessors to the valid bits of `self`.
     pub(super) fn iter_mut(&mut self) -> Bits256BitsIterMut {
-        self.bits.iter_mut(self.len as u16)
+        let ones = self.ones();
+        self.bits.iter_mut(self.len as u16, ones)
     }
 }
 
@@ -124,6 +168,7 @@ mod tests {
         Bits256,
         ChunkRef,
     };
+    use core::cell::Cell;
 
     fn is_populated_bit_set(index: u8) -> bool {
         (index % 5) == 0 || (index % 13) == 0
@@ -159,7 +204,8 @@ mod tests {
     fn exclusive_works() {
         let len: u8 = 100;
         let mut bits = populated_bits256();
-        let mut cref = ChunkRef::exclusive(&mut bits, len as u32);
+        let ones = Cell::new(bits.count_ones());
+        let mut cref = ChunkRef::exclusive(&mut bits, len as u32, &ones);
         assert_eq!(cref.len(), len as u32);
         // `get` and `get_mut` works:
         for i in 0..len {
@@ -180,21 +226,24 @@ mod tests {
     #[test]
     fn position_first_zero_works() {
         let len = 256;
-        let mut zeros = Default::default();
-        let mut cref = ChunkRef::exclusive(&mut zeros, len);
+        let mut zeros = Bits256::default();
+        let ones = Cell::new(0);
+        let mut cref = ChunkRef::exclusive(&mut zeros, len, &ones);
         for i in 0..len {
             assert_eq!(cref.position_first_zero(), Some(i as u8));
             cref.get_mut(i as u8).unwrap().set();
         }
         // Now all bits are set to `1`:
         assert_eq!(cref.position_first_zero(), None);
+        assert_eq!(ones.get(), len);
     }
 
     #[test]
     fn iter_mut_works() {
         let len = 100;
-        let mut zeros = Default::default();
-        let mut cref = ChunkRef::exclusive(&mut zeros, len);
+        let mut zeros = Bits256::default();
+        let ones = Cell::new(0);
+        let mut cref = ChunkRef::exclusive(&mut zeros, len, &ones);
         // Initialize all bits with 0 and set them to 1 via `iter_mut`.
         // Then check if they are 1:
         for mut byte in cref.iter_mut() {
@@ -202,5 +251,6 @@ mod tests {
             byte.set();
         }
         assert!(cref.iter().all(|byte| byte));
+        assert_eq!(ones.get(), len);
     }
 }
@@ -16,7 +16,14 @@ use super::{
     BitsIter,
     Bitvec as StorageBitvec,
 };
-use core::iter::FromIterator;
+use core::{
+    iter::FromIterator,
+    ops::{
+        BitAndAssign,
+        BitOrAssign,
+        BitXorAssign,
+    },
+};
 
 impl Default for StorageBitvec {
     fn default() -> Self {
@@ -73,3 +80,36 @@ impl<'a> IntoIterator for &'a StorageBitvec {
         self.bits()
     }
 }
+
+impl<'a> BitAndAssign<&'a StorageBitvec> for StorageBitvec {
+    /// Sets every bit to the logical AND of itself and the bit of `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// If `self` and `rhs` have different lengths.
+    fn bitand_assign(&mut self, rhs: &'a StorageBitvec) {
+        self.zip_chunks_with(rhs, |lhs, rhs| lhs & rhs)
+    }
+}
+
+impl<'a> BitOrAssign<&'a StorageBitvec> for StorageBitvec {
+    /// Sets every bit to the logical OR of itself and the bit of `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// If `self` and `rhs` have different lengths.
+    fn bitor_assign(&mut self, rhs: &'a StorageBitvec) {
+        self.zip_chunks_with(rhs, |lhs, rhs| lhs | rhs)
+    }
+}
+
+impl<'a> BitXorAssign<&'a StorageBitvec> for StorageBitvec {
+    /// Sets every bit to the logical XOR of itself and the bit of `rhs`.
+    ///
+    /// # Panics
+    ///
+    /// If `self` and `rhs` have different lengths.
+    fn bitxor_assign(&mut self, rhs: &'a StorageBitvec) {
+        self.zip_chunks_with(rhs, |lhs, rhs| lhs ^ rhs)
+    }
+}
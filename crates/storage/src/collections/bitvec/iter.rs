@@ -25,7 +25,10 @@ use crate::collections::vec::{
     Iter as StorageVecIter,
     IterMut as StorageVecIterMut,
 };
-use core::cmp::min;
+use core::{
+    cell::Cell,
+    cmp::min,
+};
 
 /// Iterator over the bits of a storage bit vector.
 #[derive(Debug, Copy, Clone)]
@@ -262,14 +265,19 @@ pub struct Bits256IterMut<'a> {
     iter: StorageVecIterMut<'a, Bits256>,
     /// The remaining bits to be yielded.
     remaining: u32,
+    /// The number of set bits of the storage bit vector.
+    ones: &'a Cell<u32>,
 }
 
 impl<'a> Bits256IterMut<'a> {
     /// Creates a new 256-bit chunks iterator over the given storage bitvector.
     pub(super) fn new(bitvec: &'a mut StorageBitvec) -> Self {
+        let remaining = bitvec.len();
+        let (bits, ones) = bitvec.bits_and_ones_mut();
         Self {
-            remaining: bitvec.len(),
-            iter: bitvec.bits.iter_mut(),
+            remaining,
+            iter: bits.iter_mut(),
+            ones,
         }
     }
 }
@@ -280,9 +288,10 @@ impl<'a> Iterator for Bits256IterMut<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let len = min(256, self.remaining);
         self.remaining = self.remaining.saturating_sub(256);
+        let ones = self.ones;
         self.iter
             .next()
-            .map(|bits256| ChunkRef::exclusive(bits256, len))
+            .map(|bits256| ChunkRef::exclusive(bits256, len, ones))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -301,10 +310,56 @@ impl<'a> DoubleEndedIterator for Bits256IterMut<'a> {
         if len == 0 {
             len = 256;
         }
+        let ones = self.ones;
         self.iter
             .next_back()
-            .map(|bits256| ChunkRef::exclusive(bits256, len))
+            .map(|bits256| ChunkRef::exclusive(bits256, len, ones))
     }
 }
 
 impl<'a> ExactSizeIterator for Bits256IterMut<'a> {}
+
+/// Iterator over the positions of either all set or all unset bits
+/// of a storage bit vector.
+#[derive(Debug, Copy, Clone)]
+pub struct PositionsIter<'a> {
+    /// The value of the bits whose positions are yielded.
+    value: bool,
+    /// The iterator over the 256-bit chunks of the storage bit vector.
+    bits256_iter: Bits256Iter<'a>,
+    /// The index of the next 256-bit chunk.
+    next_chunk: u32,
+    /// The offset of the current 256-bit chunk.
+    offset: u32,
+    /// The selected bits of the current 256-bit chunk that are yet to be yielded.
+    current: Bits256,
+}
+
+impl<'a> PositionsIter<'a> {
+    /// Creates a new iterator yielding the positions of the bits equal to `value`.
+    pub(super) fn new(bitvec: &'a StorageBitvec, value: bool) -> Self {
+        Self {
+            value,
+            bits256_iter: bitvec.iter_chunks(),
+            next_chunk: 0,
+            offset: 0,
+            current: Bits256::default(),
+        }
+    }
+}
+
+impl<'a> Iterator for PositionsIter<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(position) = self.current.take_first_set() {
+                return Some(self.offset + position as u32)
+            }
+            let chunk = self.bits256_iter.next()?;
+            self.offset = self.next_chunk * 256;
+            self.next_chunk += 1;
+            self.current = chunk.select(self.value);
+        }
+    }
+}
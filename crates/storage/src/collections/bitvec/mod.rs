@@ -32,6 +32,7 @@ pub use self::{
     iter::{
         BitsIter,
         BitsIterMut,
+        PositionsIter,
     },
 };
 use self::{
@@ -49,6 +50,10 @@ use crate::{
     Lazy,
     Vec as StorageVec,
 };
+use core::{
+    cell::Cell,
+    ops::Range,
+};
 
 /// The index of a bit pack within the bit vector.
 type Index = u32;
@@ -70,26 +75,39 @@ type Bits64 = u64;
 /// Allows to `push`, `pop`, inspect and manipulate the underlying bits.
 #[derive(Debug)]
 pub struct Bitvec {
-    /// The length of the bit vector.
-    len: Lazy<u32>,
+    /// The length of the bit vector and the number of its set bits.
+    header: Lazy<Header>,
     /// The bits of the bit vector.
     ///
     /// Organized in packs of 256 bits.
     bits: StorageVec<Bits256>,
 }
 
+/// Stores general commonly required information about the storage bit vector.
+#[derive(Debug, Default, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header {
+    /// The length of the bit vector.
+    len: u32,
+    /// The number of bits set to `1` (`true`).
+    ///
+    /// Kept up to date by every operation that mutates the bits so that
+    /// counting them does not require to load all 256-bit chunks.
+    ones: u32,
+}
+
 impl Bitvec {
     /// Creates a new empty bit vector.
     pub fn new() -> Self {
         Self {
-            len: Lazy::from(0),
+            header: Lazy::new(Header::default()),
             bits: StorageVec::new(),
         }
     }
 
     /// Returns the length of the bit vector in bits.
     pub fn len(&self) -> u32 {
-        *self.len
+        self.header.len
     }
 
     /// Returns `true` if the bit vector is empty.
@@ -107,6 +125,21 @@ impl Bitvec {
         (self.bits.len() * 256) as u64
     }
 
+    /// Returns the number of bits set to `1` (`true`).
+    ///
+    /// # Note
+    ///
+    /// The number of set bits is cached in the same storage cell as the
+    /// length of the bit vector, so this does not load any 256-bit chunks.
+    pub fn count_ones(&self) -> u32 {
+        self.header.ones
+    }
+
+    /// Returns the number of bits set to `0` (`false`).
+    pub fn count_zeros(&self) -> u32 {
+        self.len() - self.count_ones()
+    }
+
     /// Returns an iterator over the bits of the storage bit vector.
     pub fn bits(&self) -> BitsIter {
         BitsIter::new(self)
@@ -117,6 +150,24 @@ impl Bitvec {
         BitsIterMut::new(self)
     }
 
+    /// Returns an iterator over the positions of all bits set to `1` (`true`).
+    ///
+    /// # Note
+    ///
+    /// Loads every 256-bit chunk only once and skips over unset bits word-wise.
+    pub fn iter_ones(&self) -> PositionsIter {
+        PositionsIter::new(self, true)
+    }
+
+    /// Returns an iterator over the positions of all bits set to `0` (`false`).
+    ///
+    /// # Note
+    ///
+    /// Loads every 256-bit chunk only once and skips over set bits word-wise.
+    pub fn iter_zeros(&self) -> PositionsIter {
+        PositionsIter::new(self, false)
+    }
+
     /// Returns an iterator over the 256-bit chunks of the storage bit vector.
     pub(super) fn iter_chunks(&self) -> Bits256Iter {
         Bits256Iter::new(self)
@@ -127,6 +178,17 @@ impl Bitvec {
         Bits256IterMut::new(self)
     }
 
+    /// Returns exclusive access to the 256-bit chunks and to the number of set bits.
+    ///
+    /// # Note
+    ///
+    /// All mutable accesses to the bits of the bit vector have to go through
+    /// this method so that they update the cached number of set bits.
+    fn bits_and_ones_mut(&mut self) -> (&mut StorageVec<Bits256>, &Cell<u32>) {
+        let header = Lazy::get_mut(&mut self.header);
+        (&mut self.bits, Cell::from_mut(&mut header.ones))
+    }
+
     /// Splits the given index into a 256-bit pack index and bit position index.
     fn split_index(&self, at: Index) -> Option<(Index, Index256)> {
         if at >= self.len() {
@@ -144,19 +206,12 @@ impl Bitvec {
         Some((bits256, pos256))
     }
 
-    /// Returns the mutable access pair to the underlying 256-bits pack and bit.
-    ///
-    /// Returns `None` if the given index is out of bounds.
-    fn get_bits256_mut(&mut self, at: Index) -> Option<(&mut Bits256, Index256)> {
-        let (index, pos256) = self.split_index(at)?;
-        let bits256 = self.bits.get_mut(index).expect("index is out of bounds");
-        Some((bits256, pos256))
-    }
-
     /// Returns a mutable bit access to the bit at the given index if any.
     fn get_access_mut(&mut self, at: Index) -> Option<BitRefMut> {
-        self.get_bits256_mut(at)
-            .map(|(bits256, pos256)| BitRefMut::new(bits256, pos256))
+        let (index, pos256) = self.split_index(at)?;
+        let (bits, ones) = self.bits_and_ones_mut();
+        let bits256 = bits.get_mut(index).expect("index is out of bounds");
+        Some(BitRefMut::new(bits256, pos256, ones))
     }
 
     /// Returns the value of the bit at the given index if any.
@@ -190,8 +245,9 @@ impl Bitvec {
         use core::cmp::min;
         let chunk_id = at / 256;
         let chunk_len = min(256, self.len() - at);
-        let bits256 = self.bits.get_mut(chunk_id).expect("index is out of bounds");
-        Some(ChunkRef::exclusive(bits256, chunk_len))
+        let (bits, ones) = self.bits_and_ones_mut();
+        let bits256 = bits.get_mut(chunk_id).expect("index is out of bounds");
+        Some(ChunkRef::exclusive(bits256, chunk_len, ones))
     }
 
     /// Returns the first bit of the bit vector.
@@ -269,15 +325,16 @@ impl Bitvec {
                 // If `value` is `true` set its first bit to `1`.
                 bits256.set(0);
                 debug_assert_eq!(bits256.get(0), true);
+                self.header.ones += 1;
             };
             self.bits.push(bits256);
-            *self.len += 1;
+            self.header.len += 1;
         } else {
             // Case: The last 256-bit pack has unused bits:
             // - Set last bit of last 256-bit pack to the given value.
             // - Opt.: Since bits are initialized as 0 we only need
             //         to mutate this value if `value` is `true`.
-            *self.len += 1;
+            self.header.len += 1;
             if value {
                 self.last_mut()
                     .expect("must have at least a valid bit in this case")
@@ -301,7 +358,77 @@ impl Bitvec {
         let mut access = self.last_mut().expect("must be some if non-empty");
         let popped = access.get();
         access.reset();
-        *self.len -= 1;
+        self.header.len -= 1;
         Some(popped)
     }
+
+    /// Sets all bits within the given range to the given value.
+    ///
+    /// # Note
+    ///
+    /// Every 256-bit chunk that intersects the range is loaded once in order
+    /// to keep the number of set bits up to date and is then manipulated
+    /// word-wise.
+    ///
+    /// # Panics
+    ///
+    /// If the range is out of bounds of the bit vector.
+    pub fn set_range(&mut self, range: Range<Index>, value: bool) {
+        let Range { start, end } = range;
+        assert!(
+            start <= end && end <= self.len(),
+            "range {}..{} is out of bounds for storage bit vector of length {}",
+            start,
+            end,
+            self.len(),
+        );
+        use core::cmp::min;
+        let (bits, ones) = self.bits_and_ones_mut();
+        let mut at = start;
+        while at < end {
+            let chunk_id = at / 256;
+            let chunk_offset = chunk_id * 256;
+            let lo = (at - chunk_offset) as u16;
+            let hi = min(end - chunk_offset, 256) as u16;
+            let bits256 = bits.get_mut(chunk_id).expect("chunk index is within bounds");
+            let old_ones = bits256.count_ones();
+            bits256.set_range_to(lo, hi, value);
+            ones.set(ones.get() + bits256.count_ones() - old_ones);
+            at = chunk_offset + hi as u32;
+        }
+    }
+
+    /// Combines the bits of `self` with the bits of `other` in place using `op`.
+    ///
+    /// # Note
+    ///
+    /// Only 256-bit chunks whose value actually changes are written back
+    /// to the contract storage. `op` must map two zero words to zero so
+    /// that the unused trailing bits of the last chunk remain unset.
+    ///
+    /// # Panics
+    ///
+    /// If `self` and `other` have different lengths.
+    fn zip_chunks_with<F>(&mut self, other: &Self, op: F)
+    where
+        F: Fn(Bits64, Bits64) -> Bits64,
+    {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "bitwise operation on storage bit vectors of different lengths",
+        );
+        for chunk_id in 0..self.bits.len() {
+            let lhs = self.bits.get(chunk_id).expect("chunk index is within bounds");
+            let rhs = other.bits.get(chunk_id).expect("chunk index is within bounds");
+            let combined = lhs.zip_with(rhs, &op);
+            if combined != *lhs {
+                self.header.ones =
+                    self.header.ones + combined.count_ones() - lhs.count_ones();
+                self.bits
+                    .set(chunk_id, combined)
+                    .expect("chunk index is within bounds");
+            }
+        }
+    }
 }
@@ -15,6 +15,7 @@
 use super::{
     Bits256,
     Bitvec as StorageBitvec,
+    Header,
 };
 use crate::{
     traits::{
@@ -37,15 +38,28 @@ const _: () = {
         traits::StorageLayout,
     };
     use ink_metadata::layout::{
+        CellLayout,
         FieldLayout,
         Layout,
+        LayoutKey,
         StructLayout,
     };
 
+    impl StorageLayout for Header {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            Layout::Cell(CellLayout::new::<Header>(LayoutKey::from(
+                key_ptr.advance_by(1),
+            )))
+        }
+    }
+
     impl StorageLayout for StorageBitvec {
         fn layout(key_ptr: &mut KeyPtr) -> Layout {
             Layout::Struct(StructLayout::new(vec![
-                FieldLayout::new("len", <Lazy<u32> as StorageLayout>::layout(key_ptr)),
+                FieldLayout::new(
+                    "header",
+                    <Lazy<Header> as StorageLayout>::layout(key_ptr),
+                ),
                 FieldLayout::new(
                     "elems",
                     <StorageVec<Bits256> as StorageLayout>::layout(key_ptr),
@@ -55,6 +69,29 @@ const _: () = {
     }
 };
 
+impl SpreadLayout for Header {
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = false;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl PackedLayout for Header {
+    fn pull_packed(&mut self, _at: &Key) {}
+    fn push_packed(&self, _at: &Key) {}
+    fn clear_packed(&self, _at: &Key) {}
+}
+
 impl SpreadLayout for Bits256 {
     const FOOTPRINT: u64 = 1;
     const REQUIRES_DEEP_CLEAN_UP: bool = false;
@@ -83,18 +120,18 @@ impl SpreadLayout for StorageBitvec {
 
     fn pull_spread(ptr: &mut KeyPtr) -> Self {
         Self {
-            len: SpreadLayout::pull_spread(ptr),
+            header: SpreadLayout::pull_spread(ptr),
             bits: SpreadLayout::pull_spread(ptr),
         }
     }
 
     fn push_spread(&self, ptr: &mut KeyPtr) {
-        SpreadLayout::push_spread(&self.len, ptr);
+        SpreadLayout::push_spread(&self.header, ptr);
         SpreadLayout::push_spread(&self.bits, ptr);
     }
 
     fn clear_spread(&self, ptr: &mut KeyPtr) {
-        SpreadLayout::clear_spread(&self.len, ptr);
+        SpreadLayout::clear_spread(&self.header, ptr);
         SpreadLayout::clear_spread(&self.bits, ptr);
     }
 }
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::Bitvec as StorageBitvec;
+use super::{
+    Bits256,
+    Bitvec as StorageBitvec,
+};
 use crate::traits::{
     KeyPtr,
     SpreadLayout,
@@ -114,6 +117,37 @@ fn double_ended_iter_works() {
     assert_eq!(None, iter.next_back());
 }
 
+#[test]
+fn iter_ones_and_zeros_works() {
+    /// Asserts that `iter_ones` and `iter_zeros` agree with a naive bit-by-bit loop.
+    fn assert_positions(bitvec: &StorageBitvec) {
+        let ones = (0..bitvec.len())
+            .filter(|&i| bitvec.get(i) == Some(true))
+            .collect::<Vec<_>>();
+        let zeros = (0..bitvec.len())
+            .filter(|&i| bitvec.get(i) == Some(false))
+            .collect::<Vec<_>>();
+        assert_eq!(bitvec.iter_ones().collect::<Vec<_>>(), ones);
+        assert_eq!(bitvec.iter_zeros().collect::<Vec<_>>(), zeros);
+    }
+
+    // Empty bit vector:
+    let empty = StorageBitvec::new();
+    assert_eq!(empty.iter_ones().next(), None);
+    assert_eq!(empty.iter_zeros().next(), None);
+    // Sparse and dense patterns:
+    assert_positions(&bitvec_600());
+    assert_positions(&(0..600).map(|i| i % 97 == 0).collect::<StorageBitvec>());
+    assert_positions(&(0..600).map(|i| i % 97 != 0).collect::<StorageBitvec>());
+    // Bits at word and chunk boundaries:
+    let boundaries = [0, 31, 32, 63, 64, 127, 128, 255, 256, 511];
+    let bitvec = (0..513)
+        .map(|i| boundaries.contains(&i))
+        .collect::<StorageBitvec>();
+    assert_eq!(bitvec.iter_ones().collect::<Vec<_>>(), boundaries);
+    assert_positions(&bitvec);
+}
+
 #[test]
 fn push_works() {
     let mut bitvec = StorageBitvec::new();
@@ -176,6 +210,325 @@ fn pop_works() {
     assert!(bitvec.last_mut().is_none());
 }
 
+#[test]
+fn push_pop_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        // Pseudo-random bit sequence that fills exactly two 256-bit chunks:
+        let mut seed = 0x9e37_79b9_u32;
+        let bits = (0..512)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                seed % 2 == 0
+            })
+            .collect::<Vec<_>>();
+        let root_key = Key::from([0x42; 32]);
+        let mut bv1 = StorageBitvec::new();
+        for &bit in &bits {
+            bv1.push(bit);
+        }
+        assert_eq!((bv1.len(), bv1.capacity()), (512, 512));
+        SpreadLayout::push_spread(&bv1, &mut KeyPtr::from(root_key));
+        // Pop across the chunk boundary and flush again:
+        let mut bv2 =
+            <StorageBitvec as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        for &bit in bits[200..].iter().rev() {
+            assert_eq!(bv2.pop(), Some(bit));
+        }
+        assert_eq!(bv2.len(), 200);
+        SpreadLayout::push_spread(&bv2, &mut KeyPtr::from(root_key));
+        // Pushing again must not observe stale bits of popped positions:
+        let mut bv3 =
+            <StorageBitvec as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(bv3.bits().eq(bits[..200].iter().copied()));
+        for _ in 200..512 {
+            bv3.push(false);
+        }
+        let ones = bits[..200].iter().filter(|&&bit| bit).count() as u32;
+        assert_eq!(bv3.count_ones(), ones);
+        // Pop everything including the pushed zeros:
+        for _ in 200..512 {
+            assert_eq!(bv3.pop(), Some(false));
+        }
+        for &bit in bits[..200].iter().rev() {
+            assert_eq!(bv3.pop(), Some(bit));
+        }
+        assert_eq!(bv3.pop(), None);
+        assert_eq!(bv3.count_ones(), 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn count_ones_and_zeros_works() {
+    /// Asserts that `count_ones` and `count_zeros` agree with a naive bit-by-bit count.
+    fn assert_counts(bitvec: &StorageBitvec) {
+        let ones = bitvec.bits().filter(|&bit| bit).count() as u32;
+        assert_eq!(bitvec.count_ones(), ones);
+        assert_eq!(bitvec.count_zeros(), bitvec.len() - ones);
+    }
+
+    let mut bitvec = StorageBitvec::new();
+    assert_eq!((bitvec.count_ones(), bitvec.count_zeros()), (0, 0));
+    bitvec = bitvec_600();
+    assert_counts(&bitvec);
+    // Pseudo-random sequence of mutations:
+    let mut seed = 0x2545_f491_u32;
+    for _ in 0..200 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        match seed % 4 {
+            0 => bitvec.push(seed % 3 == 0),
+            1 => {
+                bitvec.pop();
+            }
+            2 => {
+                if let Some(mut bit) = bitvec.get_mut(seed % bitvec.len().max(1)) {
+                    bit.flip();
+                }
+            }
+            _ => {
+                let start = seed % (bitvec.len() + 1);
+                let end = start + (seed >> 8) % (bitvec.len() - start + 1);
+                bitvec.set_range(start..end, seed % 2 == 0);
+            }
+        }
+        assert_counts(&bitvec);
+    }
+}
+
+#[test]
+fn cached_count_ones_matches_recount() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        /// Recounts the set bits of all 256-bit chunks of the bit vector.
+        fn recount_ones(bitvec: &StorageBitvec) -> u32 {
+            bitvec.bits.iter().map(Bits256::count_ones).sum()
+        }
+
+        let root_key = Key::from([0x42; 32]);
+        let mut bitvec = bitvec_600();
+        let mut other = bitvec_600();
+        assert_eq!(bitvec.count_ones(), recount_ones(&bitvec));
+        // Pseudo-random sequence of mutations through all mutable accessors:
+        let mut seed = 0x6a09_e667_u32;
+        for round in 0..300 {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            let len = bitvec.len();
+            match seed % 9 {
+                0 => bitvec.push(seed % 3 == 0),
+                1 => {
+                    bitvec.pop();
+                }
+                2 => {
+                    if let Some(mut bit) = bitvec.get_mut(seed % len.max(1)) {
+                        bit.set_to(seed % 3 == 0);
+                    }
+                }
+                3 => {
+                    if let Some(mut bit) = bitvec.last_mut() {
+                        bit.flip();
+                    }
+                }
+                4 => {
+                    for mut bit in bitvec.bits_mut().step_by(1 + (seed % 7) as usize) {
+                        bit.xor(true);
+                    }
+                }
+                5 => {
+                    if let Some(mut chunk) = bitvec.get_chunk_mut(seed % len.max(1)) {
+                        let index = (seed >> 8) as u8;
+                        if let Some(mut bit) = chunk.get_mut(index) {
+                            bit.or(true);
+                        }
+                    }
+                }
+                6 => {
+                    let start = seed % (len + 1);
+                    let end = start + (seed >> 8) % (len - start + 1);
+                    bitvec.set_range(start..end, seed % 2 == 0);
+                }
+                7 => {
+                    other = (0..len).map(|i| (i ^ seed) % 3 == 0).collect();
+                    match (seed >> 8) % 3 {
+                        0 => bitvec &= &other,
+                        1 => bitvec |= &other,
+                        _ => bitvec ^= &other,
+                    }
+                }
+                _ => {
+                    // Flush and reload the bit vector from the contract storage:
+                    SpreadLayout::push_spread(&bitvec, &mut KeyPtr::from(root_key));
+                    let reloaded = <StorageBitvec as SpreadLayout>::pull_spread(
+                        &mut KeyPtr::from(root_key),
+                    );
+                    // Prevent the flushed instance from clearing the storage.
+                    core::mem::forget(core::mem::replace(&mut bitvec, reloaded));
+                }
+            }
+            assert_eq!(
+                bitvec.count_ones(),
+                recount_ones(&bitvec),
+                "cached count diverged in round {}",
+                round,
+            );
+            assert_eq!(bitvec.count_zeros(), bitvec.len() - bitvec.count_ones());
+        }
+        assert_eq!(other.count_ones(), recount_ones(&other));
+        Ok(())
+    })
+}
+
+#[test]
+fn set_range_works() {
+    let ranges = [
+        // Empty ranges:
+        0..0,
+        300..300,
+        // Ranges within a single chunk:
+        3..17,
+        250..256,
+        // Ranges ending exactly on a chunk boundary:
+        0..256,
+        100..512,
+        // Ranges spanning many chunks:
+        255..513,
+        0..600,
+    ];
+    for range in ranges.iter() {
+        for &value in &[true, false] {
+            let mut bitvec = bitvec_600();
+            bitvec.set_range(range.clone(), value);
+            assert_eq!(bitvec.len(), 600);
+            for i in 0..bitvec.len() {
+                let expected = if range.contains(&i) {
+                    value
+                } else {
+                    (i % 5) == 0 || (i % 13) == 0
+                };
+                assert_eq!(bitvec.get(i), Some(expected));
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "range 500..601 is out of bounds for storage bit vector of length 600"
+)]
+fn set_range_out_of_bounds_fails() {
+    let mut bitvec = bitvec_600();
+    bitvec.set_range(500..601, true);
+}
+
+#[test]
+fn set_range_loads_every_chunk_once() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let bv1 = bitvec_600();
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&bv1, &mut KeyPtr::from(root_key));
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let mut bv2 =
+            <StorageBitvec as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        // Load the length fields before counting reads:
+        assert_eq!((bv2.len(), bv2.capacity()), (600, 768));
+        let (base_reads, _) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        bv2.set_range(256..512, true);
+        bv2.set_range(200..600, true);
+        let (reads, _) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        // The covered chunks are loaded once in order to keep the number
+        // of set bits up to date.
+        assert_eq!(reads - base_reads, 3);
+        SpreadLayout::push_spread(&bv2, &mut KeyPtr::from(root_key));
+        let bv3 =
+            <StorageBitvec as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(bv3.bits().skip(200).all(|bit| bit));
+        assert_eq!(bv3.count_ones(), bv3.bits().filter(|&bit| bit).count() as u32);
+        assert!(bv3.bits().take(200).eq(bv1.bits().take(200)));
+        Ok(())
+    })
+}
+
+#[test]
+fn bitwise_assign_ops_work() {
+    let lhs = bitvec_600();
+    let rhs = (0..600).map(|i| i % 3 == 0).collect::<StorageBitvec>();
+    let check = |op: fn(&mut StorageBitvec, &StorageBitvec),
+                 reference: fn(bool, bool) -> bool| {
+        let mut result = lhs.bits().collect::<StorageBitvec>();
+        op(&mut result, &rhs);
+        assert_eq!(result.len(), 600);
+        for i in 0..600 {
+            let expected = reference(lhs.get(i).unwrap(), rhs.get(i).unwrap());
+            assert_eq!(result.get(i), Some(expected));
+        }
+        // Unused trailing bits of the last chunk must remain unset:
+        assert_eq!(result.count_ones(), result.iter_ones().count() as u32);
+    };
+    check(|lhs, rhs| *lhs &= rhs, |lhs, rhs| lhs & rhs);
+    check(|lhs, rhs| *lhs |= rhs, |lhs, rhs| lhs | rhs);
+    check(|lhs, rhs| *lhs ^= rhs, |lhs, rhs| lhs ^ rhs);
+}
+
+#[test]
+#[should_panic(expected = "bitwise operation on storage bit vectors of different lengths")]
+fn bitwise_assign_ops_with_different_lengths_fail() {
+    let mut lhs = bitvec_600();
+    let rhs = (0..599).map(|_| true).collect::<StorageBitvec>();
+    lhs |= &rhs;
+}
+
+#[test]
+fn bitwise_assign_ops_only_write_changed_chunks() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let bv1 = bitvec_600();
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&bv1, &mut KeyPtr::from(root_key));
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        // Only the bits of the second chunk are affected by the mask.
+        let mask = (0..600)
+            .map(|i| (256..512).contains(&i))
+            .collect::<StorageBitvec>();
+        let mut bv2 =
+            <StorageBitvec as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let (_, base_writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        bv2 ^= &mask;
+        SpreadLayout::push_spread(&bv2, &mut KeyPtr::from(root_key));
+        let (_, writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        // The changed chunk and the header holding the number of set bits:
+        assert_eq!(writes - base_writes, 2);
+        // An operation that changes no bits causes no writes at all:
+        let mut bv3 =
+            <StorageBitvec as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        bv3 |= &(0..600).map(|_| false).collect::<StorageBitvec>();
+        SpreadLayout::push_spread(&bv3, &mut KeyPtr::from(root_key));
+        let (_, writes_after_noop) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        assert_eq!(writes_after_noop, writes);
+        for i in 0..600 {
+            let expected = bv1.get(i).unwrap() ^ (256..512).contains(&i);
+            assert_eq!(bv3.get(i), Some(expected));
+        }
+        Ok(())
+    })
+}
+
 #[test]
 fn spread_layout_push_pull_works() -> ink_env::Result<()> {
     ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
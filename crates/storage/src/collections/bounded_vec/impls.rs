@@ -0,0 +1,75 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    BoundedVec,
+    Iter,
+    IterMut,
+};
+use crate::traits::PackedLayout;
+
+impl<T> core::ops::Index<u32> for BoundedVec<T>
+where
+    T: PackedLayout,
+{
+    type Output = T;
+
+    fn index(&self, index: u32) -> &Self::Output {
+        &self.vec[index]
+    }
+}
+
+impl<T> core::ops::IndexMut<u32> for BoundedVec<T>
+where
+    T: PackedLayout,
+{
+    fn index_mut(&mut self, index: u32) -> &mut Self::Output {
+        &mut self.vec[index]
+    }
+}
+
+impl<'a, T: 'a> IntoIterator for &'a BoundedVec<T>
+where
+    T: PackedLayout,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: 'a> IntoIterator for &'a mut BoundedVec<T>
+where
+    T: PackedLayout,
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> core::cmp::PartialEq for BoundedVec<T>
+where
+    T: PartialEq + PackedLayout,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.capacity() == other.capacity() && self.vec == other.vec
+    }
+}
+
+impl<T> core::cmp::Eq for BoundedVec<T> where T: Eq + PackedLayout {}
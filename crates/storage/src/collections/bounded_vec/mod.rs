@@ -0,0 +1,237 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage vector that never grows beyond a fixed number of elements.
+
+mod impls;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    collections::vec::{
+        IndexOutOfBounds,
+        Iter,
+        IterMut,
+        Vec as StorageVec,
+    },
+    lazy::Lazy,
+    traits::PackedLayout,
+};
+use ink_prelude::vec::Vec;
+
+/// A contiguous growable array type with an upper bound on its length.
+///
+/// # Note
+///
+/// The capacity is chosen upon construction and stored in the contract
+/// storage alongside the elements so that instances loaded from the contract
+/// storage enforce the same bound. Operations that would grow the vector
+/// beyond its capacity fail with a [`CapacityError`] and leave the vector
+/// unchanged.
+///
+/// Apart from that the bounded vector behaves like the storage [`Vec`](`crate::Vec`).
+#[derive(Debug)]
+pub struct BoundedVec<T>
+where
+    T: PackedLayout,
+{
+    /// The maximum number of elements.
+    capacity: Lazy<u32>,
+    /// The elements of the bounded vector.
+    vec: StorageVec<T>,
+}
+
+/// The operation would grow the vector beyond its capacity.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CapacityError;
+
+impl<T> BoundedVec<T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new empty bounded vector that holds at most `capacity` elements.
+    pub fn new(capacity: u32) -> Self {
+        Self {
+            capacity: Lazy::new(capacity),
+            vec: StorageVec::new(),
+        }
+    }
+
+    /// Returns the number of elements in the vector, also referred to as its length.
+    pub fn len(&self) -> u32 {
+        self.vec.len()
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Returns the maximum number of elements the vector can hold.
+    pub fn capacity(&self) -> u32 {
+        *self.capacity
+    }
+
+    /// Returns `true` if the vector holds as many elements as its capacity.
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity()
+    }
+
+    /// Returns an iterator yielding shared references to all elements of the vector.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage vectors.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter(&self) -> Iter<T> {
+        self.vec.iter()
+    }
+
+    /// Returns an iterator yielding exclusive references to all elements of the vector.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage vectors.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter_mut(&mut self) -> IterMut<T> {
+        self.vec.iter_mut()
+    }
+
+    /// Returns a shared reference to the first element if any.
+    pub fn first(&self) -> Option<&T> {
+        self.vec.first()
+    }
+
+    /// Returns a shared reference to the last element if any.
+    pub fn last(&self) -> Option<&T> {
+        self.vec.last()
+    }
+
+    /// Returns a shared reference to the indexed element.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get(&self, index: u32) -> Option<&T> {
+        self.vec.get(index)
+    }
+
+    /// Returns an exclusive reference to the first element if any.
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.vec.first_mut()
+    }
+
+    /// Returns an exclusive reference to the last element if any.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.vec.last_mut()
+    }
+
+    /// Returns an exclusive reference to the indexed element.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get_mut(&mut self, index: u32) -> Option<&mut T> {
+        self.vec.get_mut(index)
+    }
+
+    /// Appends an element to the back of the vector.
+    ///
+    /// # Errors
+    ///
+    /// If the vector is already full.
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError)
+        }
+        self.vec.push(value);
+        Ok(())
+    }
+
+    /// Inserts an element at position `index` shifting all elements after it
+    /// to the right.
+    ///
+    /// # Note
+    ///
+    /// This operation has to move all elements after `index` and therefore
+    /// performs `O(n)` contract storage reads and writes.
+    ///
+    /// # Errors
+    ///
+    /// If the vector is already full.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length of the vector.
+    pub fn insert(&mut self, index: u32, value: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError)
+        }
+        self.vec.insert(index, value);
+        Ok(())
+    }
+
+    /// Appends all elements of the iterator to the back of the vector.
+    ///
+    /// # Note
+    ///
+    /// The elements are collected in memory first in order to check whether
+    /// they fit into the vector.
+    ///
+    /// # Errors
+    ///
+    /// If the vector cannot hold all of the elements. In this case none of
+    /// the elements are appended.
+    pub fn extend<I>(&mut self, iter: I) -> Result<(), CapacityError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let remaining = self.capacity().saturating_sub(self.len()) as usize;
+        let values = iter.into_iter().take(remaining + 1).collect::<Vec<_>>();
+        if values.len() > remaining {
+            return Err(CapacityError)
+        }
+        for value in values {
+            self.vec.push(value);
+        }
+        Ok(())
+    }
+
+    /// Pops the last element from the vector and returns it.
+    ///
+    /// Returns `None` if the vector is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.vec.pop()
+    }
+
+    /// Removes the indexed element from the vector and returns it.
+    ///
+    /// The last element of the vector is put into the indexed slot.
+    /// Returns `None` and does not mutate the vector if the index is out of bounds.
+    pub fn swap_remove(&mut self, n: u32) -> Option<T> {
+        self.vec.swap_remove(n)
+    }
+
+    /// Sets the elements at the given index to the new value.
+    pub fn set(&mut self, index: u32, new_value: T) -> Result<(), IndexOutOfBounds> {
+        self.vec.set(index, new_value)
+    }
+
+    /// Removes all elements from this vector.
+    ///
+    /// The capacity of the vector is not changed.
+    pub fn clear(&mut self) {
+        self.vec.clear()
+    }
+}
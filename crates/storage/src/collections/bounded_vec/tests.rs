@@ -0,0 +1,171 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    BoundedVec,
+    CapacityError,
+};
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+
+/// Creates a bounded vector with a capacity of 4 holding the given elements.
+fn bvec_from_slice(slice: &[u8]) -> BoundedVec<u8> {
+    let mut bvec = BoundedVec::new(4);
+    bvec.extend(slice.iter().copied())
+        .expect("slice must fit into the bounded vector");
+    bvec
+}
+
+/// Asserts that the bounded vector holds exactly the given elements.
+fn assert_elems(bvec: &BoundedVec<u8>, expected: &[u8]) {
+    assert_eq!(bvec.len() as usize, expected.len());
+    assert!(bvec.iter().eq(expected.iter()));
+}
+
+#[test]
+fn new_works() {
+    let bvec = <BoundedVec<u8>>::new(4);
+    assert!(bvec.is_empty());
+    assert!(!bvec.is_full());
+    assert_eq!(bvec.capacity(), 4);
+    assert_eq!(bvec.first(), None);
+    assert!(bvec.iter().next().is_none());
+    // A bounded vector without capacity is always full.
+    let bvec = <BoundedVec<u8>>::new(0);
+    assert!(bvec.is_full());
+}
+
+#[test]
+fn push_until_full_works() {
+    let mut bvec = <BoundedVec<u8>>::new(4);
+    for (n, value) in [b'a', b'b', b'c', b'd'].iter().enumerate() {
+        assert!(!bvec.is_full());
+        assert_eq!(bvec.push(*value), Ok(()));
+        assert_eq!(bvec.len(), n as u32 + 1);
+    }
+    assert!(bvec.is_full());
+    assert_elems(&bvec, &[b'a', b'b', b'c', b'd']);
+    assert_eq!(bvec[3], b'd');
+    assert_eq!(bvec.last(), Some(&b'd'));
+}
+
+#[test]
+fn push_beyond_capacity_fails() {
+    let mut bvec = bvec_from_slice(&[b'a', b'b', b'c', b'd']);
+    assert_eq!(bvec.push(b'e'), Err(CapacityError));
+    assert_eq!(bvec.insert(0, b'e'), Err(CapacityError));
+    assert_eq!(bvec.extend(Some(b'e')), Err(CapacityError));
+    // The vector is left unchanged:
+    assert_elems(&bvec, &[b'a', b'b', b'c', b'd']);
+    // Removing an element makes room for another one.
+    assert_eq!(bvec.pop(), Some(b'd'));
+    assert_eq!(bvec.insert(1, b'x'), Ok(()));
+    assert_elems(&bvec, &[b'a', b'x', b'b', b'c']);
+    assert_eq!(bvec.swap_remove(0), Some(b'a'));
+    assert_eq!(bvec.push(b'y'), Ok(()));
+    assert_elems(&bvec, &[b'c', b'x', b'b', b'y']);
+}
+
+#[test]
+fn extend_works() {
+    let mut bvec = bvec_from_slice(&[b'a']);
+    // Extending beyond the capacity appends none of the elements.
+    assert_eq!(bvec.extend(b"bcde".iter().copied()), Err(CapacityError));
+    assert_elems(&bvec, &[b'a']);
+    // Extending up to exactly the capacity succeeds.
+    assert_eq!(bvec.extend(b"bcd".iter().copied()), Ok(()));
+    assert_elems(&bvec, &[b'a', b'b', b'c', b'd']);
+    // Extending a full vector with nothing succeeds.
+    assert_eq!(bvec.extend(core::iter::empty()), Ok(()));
+    // Only as many elements as necessary are taken from the iterator.
+    let mut values = b"xyz".iter().copied();
+    assert_eq!(bvec.extend(&mut values), Err(CapacityError));
+    assert_eq!(values.next(), Some(b'y'));
+}
+
+#[test]
+fn get_and_set_works() {
+    let mut bvec = bvec_from_slice(&[b'a', b'b', b'c']);
+    assert_eq!(bvec.get(1), Some(&b'b'));
+    assert_eq!(bvec.get(3), None);
+    *bvec.get_mut(0).unwrap() = b'x';
+    bvec[1] = b'y';
+    assert_eq!(bvec.set(2, b'z'), Ok(()));
+    assert!(bvec.set(3, b'w').is_err());
+    for value in &mut bvec {
+        *value -= 1;
+    }
+    assert!((&bvec).into_iter().eq([b'w', b'x', b'y'].iter()));
+}
+
+#[test]
+fn clear_works() {
+    let mut bvec = bvec_from_slice(&[b'a', b'b', b'c', b'd']);
+    bvec.clear();
+    assert!(bvec.is_empty());
+    assert_eq!(bvec.capacity(), 4);
+    assert_eq!(bvec.extend(b"wxyz".iter().copied()), Ok(()));
+    assert!(bvec.is_full());
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let bvec1 = bvec_from_slice(&[b'a', b'b', b'c']);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&bvec1, &mut KeyPtr::from(root_key));
+        // Load the pushed bounded vector into another instance and check that
+        // both instances are equal:
+        let mut bvec2 =
+            <BoundedVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(bvec1, bvec2);
+        // The loaded instance enforces the same capacity:
+        assert_eq!(bvec2.capacity(), 4);
+        assert_eq!(bvec2.push(b'd'), Ok(()));
+        assert_eq!(bvec2.push(b'e'), Err(CapacityError));
+        SpreadLayout::push_spread(&bvec2, &mut KeyPtr::from(root_key));
+        let mut bvec3 =
+            <BoundedVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(bvec3.is_full());
+        assert_eq!(bvec3.insert(0, b'e'), Err(CapacityError));
+        assert_elems(&bvec3, &[b'a', b'b', b'c', b'd']);
+        // Avoid clearing the storage when dropping the instances:
+        core::mem::forget((bvec1, bvec2, bvec3));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "encountered empty storage cell")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let bvec1 = bvec_from_slice(&[b'a', b'b', b'c']);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&bvec1, &mut KeyPtr::from(root_key));
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `bvec1` and check whether
+        // loading another instance from this storage will panic since the
+        // vector's capacity property cannot read a value:
+        SpreadLayout::clear_spread(&bvec1, &mut KeyPtr::from(root_key));
+        let _ =
+            <BoundedVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        Ok(())
+    })
+    .unwrap()
+}
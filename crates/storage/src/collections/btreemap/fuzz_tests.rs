@@ -0,0 +1,100 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::BTreeMap as StorageBTreeMap;
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+use std::collections::BTreeMap as StdBTreeMap;
+
+/// Executes the operations encoded by `ops` on both a storage B-tree map and
+/// a standard `BTreeMap` and asserts that both behave the same.
+///
+/// For each `(insert, key)` the key is either inserted into or removed from
+/// both maps. Keys are reduced into a small domain so that removals hit
+/// existing keys often.
+fn apply_ops(
+    map: &mut StorageBTreeMap<u8, i32>,
+    expected: &mut StdBTreeMap<u8, i32>,
+    ops: &[(bool, u8)],
+) {
+    for (n, &(insert, key)) in ops.iter().enumerate() {
+        let key = key % 128;
+        if insert {
+            assert_eq!(map.insert(key, n as i32), expected.insert(key, n as i32));
+        } else {
+            assert_eq!(map.take(&key), expected.remove(&key));
+        }
+        assert_eq!(map.len() as usize, expected.len());
+    }
+    assert!(map.iter().eq(expected.iter()));
+    assert_eq!(map.first_key_value(), expected.iter().next());
+    assert_eq!(map.last_key_value(), expected.iter().next_back());
+}
+
+#[quickcheck]
+fn fuzz_inserts_and_removes(ops: Vec<(bool, u8)>) {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut map = <StorageBTreeMap<u8, i32>>::new();
+        let mut expected = StdBTreeMap::new();
+        apply_ops(&mut map, &mut expected, &ops);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[quickcheck]
+fn fuzz_range(ops: Vec<(bool, u8)>, start: u8, end: u8) {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut map = <StorageBTreeMap<u8, i32>>::new();
+        let mut expected = StdBTreeMap::new();
+        apply_ops(&mut map, &mut expected, &ops);
+        let (start, end) = (start.min(end), start.max(end));
+        assert!(map.range(start..end).eq(expected.range(start..end)));
+        assert!(map.range(start..=end).eq(expected.range(start..=end)));
+        assert!(map.range(..end).eq(expected.range(..end)));
+        assert!(map.range(start..).eq(expected.range(start..)));
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[quickcheck]
+fn fuzz_spread_layout_push_pull(ops1: Vec<(bool, u8)>, ops2: Vec<(bool, u8)>) {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x00; 32]);
+        let mut map = <StorageBTreeMap<u8, i32>>::new();
+        let mut expected = StdBTreeMap::new();
+        apply_ops(&mut map, &mut expected, &ops1);
+        SpreadLayout::push_spread(&map, &mut KeyPtr::from(root_key));
+
+        // Reconstruct the map from its root key and keep operating on it.
+        let mut map2: StorageBTreeMap<u8, i32> =
+            SpreadLayout::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(map2.iter().eq(expected.iter()));
+        apply_ops(&mut map2, &mut expected, &ops2);
+        SpreadLayout::push_spread(&map2, &mut KeyPtr::from(root_key));
+
+        let map3: StorageBTreeMap<u8, i32> =
+            SpreadLayout::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(map3.iter().eq(expected.iter()));
+
+        // Avoid clearing the storage when dropping the instances.
+        core::mem::forget((map, map2, map3));
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,246 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    BTreeMap as StorageBTreeMap,
+    NodeIndex,
+};
+use crate::traits::PackedLayout;
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    ops::{
+        Bound,
+        RangeBounds,
+    },
+};
+use ink_prelude::{
+    vec,
+    vec::Vec,
+};
+
+/// A position of a key/value pair within the B-tree.
+type Position = (NodeIndex, usize);
+
+/// An iterator over shared references to the elements of a storage B-tree map
+/// within a range of keys.
+#[derive(Debug, Clone)]
+pub struct Range<'a, K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    /// The storage B-tree map to iterate over.
+    map: &'a StorageBTreeMap<K, V>,
+    /// The path from the root to the next yielded key/value pair.
+    ///
+    /// Entries whose position is equal to the length of their node have
+    /// been fully traversed and are popped lazily.
+    front: Vec<Position>,
+    /// The position of the first key/value pair that is past the range if any.
+    end: Option<Position>,
+}
+
+/// Returns the key of the bound if it is not unbounded.
+fn bound_key<Q: ?Sized>(bound: Bound<&Q>) -> Option<&Q> {
+    match bound {
+        Bound::Included(key) | Bound::Excluded(key) => Some(key),
+        Bound::Unbounded => None,
+    }
+}
+
+impl<'a, K, V> Range<'a, K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    /// Creates a new iterator over the given range of the storage B-tree map.
+    pub(super) fn new<Q, R>(map: &'a StorageBTreeMap<K, V>, range: R) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        if let (Some(start_key), Some(end_key)) = (bound_key(start), bound_key(end)) {
+            if start_key > end_key {
+                panic!("range start is greater than range end in storage B-tree map")
+            }
+            if let (Bound::Excluded(_), Bound::Excluded(_)) = (start, end) {
+                if start_key == end_key {
+                    panic!(
+                        "range start and end are equal and excluded in storage B-tree map"
+                    )
+                }
+            }
+        }
+        let front = Self::seek(map, |key| {
+            match start {
+                Bound::Included(start) => key.borrow() < start,
+                Bound::Excluded(start) => key.borrow() <= start,
+                Bound::Unbounded => false,
+            }
+        });
+        let end = match end {
+            Bound::Included(end) => {
+                Self::first_position(map, &Self::seek(map, |key| key.borrow() <= end))
+            }
+            Bound::Excluded(end) => {
+                Self::first_position(map, &Self::seek(map, |key| key.borrow() < end))
+            }
+            Bound::Unbounded => None,
+        };
+        Self { map, front, end }
+    }
+
+    /// Returns the path from the root to the first key/value pair whose key
+    /// does not satisfy `skip`.
+    ///
+    /// # Note
+    ///
+    /// Requires `skip` to be monotonous with respect to the ordering of keys.
+    fn seek<F>(map: &'a StorageBTreeMap<K, V>, mut skip: F) -> Vec<Position>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let mut path = vec![];
+        let mut current = map.header.root;
+        while let Some(index) = current {
+            let node = map.node(index);
+            let pos = node
+                .keys
+                .binary_search_by(|key| {
+                    if skip(key) {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    }
+                })
+                .unwrap_err();
+            path.push((index, pos));
+            current = node.children.get(pos).copied();
+        }
+        path
+    }
+
+    /// Returns the position of the next key/value pair on the path if any.
+    fn first_position(
+        map: &'a StorageBTreeMap<K, V>,
+        path: &[Position],
+    ) -> Option<Position> {
+        path.iter()
+            .rev()
+            .find(|&&(index, pos)| pos < map.node(index).len())
+            .copied()
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let map = self.map;
+        loop {
+            let (index, pos) = *self.front.last()?;
+            let node = map.node(index);
+            if pos == node.len() {
+                self.front.pop();
+                continue
+            }
+            if self.end == Some((index, pos)) {
+                self.front.clear();
+                return None
+            }
+            if let Some(last) = self.front.last_mut() {
+                last.1 += 1;
+            }
+            // Descend to the smallest key/value pair of the subtree right of
+            // the yielded key/value pair which comes next in order.
+            let mut child = node.children.get(pos + 1).copied();
+            while let Some(index) = child {
+                self.front.push((index, 0));
+                child = map.node(index).children.first().copied();
+            }
+            return Some((&node.keys[pos], &node.values[pos]))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.front.is_empty() {
+            return (0, Some(0))
+        }
+        (0, Some(self.map.len() as usize))
+    }
+}
+
+/// An iterator over shared references to the elements of a storage B-tree map.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    /// The iterator over the full range of the map.
+    range: Range<'a, K, V>,
+    /// The number of yet to be yielded key/value pairs.
+    remaining: u32,
+}
+
+impl<'a, K, V> Iter<'a, K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    /// Creates a new iterator for the given storage B-tree map.
+    pub(super) fn new(map: &'a StorageBTreeMap<K, V>) -> Self {
+        Self {
+            range: Range::new::<K, _>(map, ..),
+            remaining: map.len(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    type Item = (&'a K, &'a V);
+
+    fn count(self) -> usize {
+        self.remaining as usize
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.range.next()?;
+        self.remaining -= 1;
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+}
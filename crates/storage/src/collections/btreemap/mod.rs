@@ -0,0 +1,639 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage B-tree map that keeps its keys in sorted order.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(all(test, feature = "ink-fuzz-tests"))]
+mod fuzz_tests;
+
+pub use self::iter::{
+    Iter,
+    Range,
+};
+use crate::{
+    collections::Stash,
+    lazy::Lazy,
+    traits::PackedLayout,
+};
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    ops::RangeBounds,
+};
+use ink_prelude::{
+    vec,
+    vec::Vec,
+};
+
+/// The index of a node within the storage B-tree map.
+type NodeIndex = u32;
+
+/// The minimum degree of the B-tree.
+///
+/// Every node except the root holds at least `MIN_DEGREE - 1` and at most
+/// `2 * MIN_DEGREE - 1` key/value pairs.
+const MIN_DEGREE: usize = 6;
+
+/// The maximum number of key/value pairs that fit into a single node.
+const CAPACITY: usize = 2 * MIN_DEGREE - 1;
+
+/// An ordered map operating on the contract storage.
+///
+/// Stores a mapping between keys and values and allows to iterate over its
+/// entries in the order of their keys.
+///
+/// # Note
+///
+/// The map is organized as a B-tree whose nodes each occupy a single storage
+/// cell holding up to `2 * MIN_DEGREE - 1` key/value pairs. This keeps the
+/// number of storage reads and writes per operation logarithmic in the number
+/// of stored elements. Nodes that are freed by removals are returned to the
+/// underlying storage stash and get reused by subsequent insertions.
+///
+/// The branching factor is fixed to a minimum degree of `6`, so every node holds
+/// at most `11` key/value pairs. It is intentionally not a type parameter: this
+/// keeps the type signature of the map in line with
+/// [`HashMap`](`crate::collections::HashMap`), and the encoding of the stored
+/// nodes depends on it, so changing it would render maps that are already stored
+/// in the contract storage unreadable.
+#[derive(Debug)]
+pub struct BTreeMap<K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    /// The root node index and the number of stored elements.
+    header: Lazy<Header>,
+    /// The nodes of the B-tree.
+    nodes: Stash<Node<K, V>>,
+}
+
+/// Stores general commonly required information about the storage B-tree map.
+#[derive(Debug, Default, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header {
+    /// The index of the root node if the map is not empty.
+    root: Option<NodeIndex>,
+    /// The number of key/value pairs stored in the map.
+    len: u32,
+}
+
+/// A node of the B-tree.
+///
+/// Leaf nodes have no children. Internal nodes have exactly one more child
+/// than they have keys. The keys of the child at index `i` are all less than
+/// `keys[i]` and the keys of the child at index `i + 1` are all greater.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Node<K, V> {
+    /// The sorted keys of the node.
+    keys: Vec<K>,
+    /// The values associated with the keys at the same positions.
+    values: Vec<V>,
+    /// The indices of the child nodes.
+    children: Vec<NodeIndex>,
+}
+
+impl<K, V> Node<K, V> {
+    /// Returns `true` if the node has no children.
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns the number of key/value pairs stored in the node.
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if no further key/value pair fits into the node.
+    fn is_full(&self) -> bool {
+        self.len() == CAPACITY
+    }
+
+    /// Returns `true` if a key/value pair can be removed from the node
+    /// without violating the minimum occupancy of non-root nodes.
+    fn can_spare(&self) -> bool {
+        self.len() >= MIN_DEGREE
+    }
+
+    /// Searches the node for the given key.
+    ///
+    /// Returns `Ok` with the position of the key if found or `Err` with the
+    /// position of the child that might contain the key otherwise.
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.binary_search_by(|probe| probe.borrow().cmp(key))
+    }
+}
+
+impl<K, V> BTreeMap<K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    /// Creates a new empty storage B-tree map.
+    pub fn new() -> Self {
+        Self {
+            header: Lazy::new(Header::default()),
+            nodes: Stash::new(),
+        }
+    }
+
+    /// Returns the number of key/value pairs stored in the map.
+    pub fn len(&self) -> u32 {
+        self.header.len
+    }
+
+    /// Returns `true` if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator yielding shared references to all key/value pairs
+    /// of the map in ascending order of their keys.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage B-tree maps.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter(&self) -> Iter<K, V> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator yielding shared references to the key/value pairs
+    /// with keys within the given range in ascending order of their keys.
+    ///
+    /// # Panics
+    ///
+    /// - If the start of the range is greater than its end.
+    /// - If the start and end of the range are equal and both are excluded.
+    pub fn range<Q, R>(&self, range: R) -> Range<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        Range::new(self, range)
+    }
+
+    /// Returns a shared reference to the node at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If the index does not refer to a node of the B-tree.
+    fn node(&self, at: NodeIndex) -> &Node<K, V> {
+        self.nodes
+            .get(at)
+            .expect("encountered invalid B-tree node index")
+    }
+
+    /// Returns an exclusive reference to the node at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If the index does not refer to a node of the B-tree.
+    fn node_mut(&mut self, at: NodeIndex) -> &mut Node<K, V> {
+        self.nodes
+            .get_mut(at)
+            .expect("encountered invalid B-tree node index")
+    }
+
+    /// Returns the node index and position of the key/value pair with the given key.
+    fn find<Q>(&self, key: &Q) -> Option<(NodeIndex, usize)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut current = self.header.root?;
+        loop {
+            let node = self.node(current);
+            match node.search(key) {
+                Ok(pos) => return Some((current, pos)),
+                Err(_) if node.is_leaf() => return None,
+                Err(pos) => current = node.children[pos],
+            }
+        }
+    }
+
+    /// Returns the node index and position of the smallest or greatest key.
+    fn find_edge(&self, ordering: Ordering) -> Option<(NodeIndex, usize)> {
+        let mut current = self.header.root?;
+        loop {
+            let node = self.node(current);
+            let pos = match ordering {
+                Ordering::Less => 0,
+                _ => node.len() - 1,
+            };
+            if node.is_leaf() {
+                return Some((current, pos))
+            }
+            current = match ordering {
+                Ordering::Less => node.children[0],
+                _ => node.children[node.len()],
+            };
+        }
+    }
+
+    /// Returns a shared reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (node, pos) = self.find(key)?;
+        Some(&self.node(node).values[pos])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (node, pos) = self.find(key)?;
+        Some(&mut self.node_mut(node).values[pos])
+    }
+
+    /// Returns `true` if there is an entry corresponding to the key in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Returns the key/value pair with the smallest key in the map if any.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        let (node, pos) = self.find_edge(Ordering::Less)?;
+        let node = self.node(node);
+        Some((&node.keys[pos], &node.values[pos]))
+    }
+
+    /// Returns the key/value pair with the greatest key in the map if any.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let (node, pos) = self.find_edge(Ordering::Greater)?;
+        let node = self.node(node);
+        Some((&node.keys[pos], &node.values[pos]))
+    }
+
+    /// Inserts a key/value pair into the map.
+    ///
+    /// Returns the previous value associated with the same key if any.
+    /// If the map did have this key present, the value is updated,
+    /// and the old value is returned. The key is not updated, though.
+    pub fn insert(&mut self, key: K, new_value: V) -> Option<V> {
+        if let Some((node, pos)) = self.find(&key) {
+            let old_value = &mut self.node_mut(node).values[pos];
+            return Some(core::mem::replace(old_value, new_value))
+        }
+        let mut root = match self.header.root {
+            Some(root) => root,
+            None => {
+                let root = self.nodes.put(Node {
+                    keys: vec![key],
+                    values: vec![new_value],
+                    children: vec![],
+                });
+                self.header.root = Some(root);
+                self.header.len = 1;
+                return None
+            }
+        };
+        if self.node(root).is_full() {
+            // The root is split preemptively so that the new key always finds
+            // room in a non-full node on the way down. This is the only way
+            // for the B-tree to grow in height.
+            let new_root = self.nodes.put(Node {
+                keys: vec![],
+                values: vec![],
+                children: vec![root],
+            });
+            self.split_child(new_root, 0);
+            self.header.root = Some(new_root);
+            root = new_root;
+        }
+        self.insert_non_full(root, key, new_value);
+        self.header.len += 1;
+        None
+    }
+
+    /// Inserts the key/value pair into the subtree of the given non-full node.
+    ///
+    /// # Note
+    ///
+    /// Expects that the key is not yet contained in the subtree.
+    fn insert_non_full(&mut self, mut current: NodeIndex, key: K, new_value: V) {
+        loop {
+            let node = self.node(current);
+            let pos = node
+                .search(&key)
+                .expect_err("the key must not be contained in the B-tree");
+            if node.is_leaf() {
+                let node = self.node_mut(current);
+                node.keys.insert(pos, key);
+                node.values.insert(pos, new_value);
+                return
+            }
+            let mut child_pos = pos;
+            if self.node(node.children[pos]).is_full() {
+                self.split_child(current, pos);
+                if self.node(current).keys[pos] < key {
+                    child_pos += 1;
+                }
+            }
+            current = self.node(current).children[child_pos];
+        }
+    }
+
+    /// Splits the full child at the given position of the parent node.
+    ///
+    /// The median key/value pair of the child is moved up into the parent node
+    /// and the upper half of the child is moved into a newly created sibling.
+    fn split_child(&mut self, parent: NodeIndex, pos: usize) {
+        let child = self.node(parent).children[pos];
+        let child = self.node_mut(child);
+        debug_assert!(child.is_full());
+        let mut sibling = Node {
+            keys: child.keys.split_off(MIN_DEGREE),
+            values: child.values.split_off(MIN_DEGREE),
+            children: vec![],
+        };
+        if !child.is_leaf() {
+            sibling.children = child.children.split_off(MIN_DEGREE);
+        }
+        let median_key = child.keys.pop().expect("a full node must have keys");
+        let median_value = child.values.pop().expect("a full node must have values");
+        let sibling = self.nodes.put(sibling);
+        let parent = self.node_mut(parent);
+        parent.keys.insert(pos, median_key);
+        parent.values.insert(pos, median_value);
+        parent.children.insert(pos + 1, sibling);
+    }
+
+    /// Removes the key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn take<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes the key from the map, returning the stored key and value if the
+    /// key was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        // Bail out early without restructuring the B-tree if the key is missing.
+        self.find(key)?;
+        let root = self.header.root.expect("a non-empty B-tree must have a root");
+        let removed = self.remove_from(root, key);
+        self.header.len -= 1;
+        self.shrink_root();
+        Some(removed)
+    }
+
+    /// Removes the key from the subtree of the given node.
+    ///
+    /// # Note
+    ///
+    /// Expects that the key is contained in the subtree and that the node is
+    /// either the root or can spare a key/value pair.
+    fn remove_from<Q>(&mut self, mut current: NodeIndex, key: &Q) -> (K, V)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        loop {
+            let node = self.node(current);
+            match node.search(key) {
+                Ok(pos) if node.is_leaf() => {
+                    let node = self.node_mut(current);
+                    return (node.keys.remove(pos), node.values.remove(pos))
+                }
+                Ok(pos) => {
+                    let (left, right) = (node.children[pos], node.children[pos + 1]);
+                    if self.node(left).can_spare() {
+                        let replacement = self.remove_edge(left, Ordering::Greater);
+                        return self.replace_at(current, pos, replacement)
+                    }
+                    if self.node(right).can_spare() {
+                        let replacement = self.remove_edge(right, Ordering::Less);
+                        return self.replace_at(current, pos, replacement)
+                    }
+                    self.merge_children(current, pos);
+                    current = left;
+                }
+                Err(pos) => {
+                    debug_assert!(!node.is_leaf());
+                    current = self.prepare_child(current, pos);
+                }
+            }
+        }
+    }
+
+    /// Removes the smallest or greatest key/value pair from the subtree of the
+    /// given node which must be able to spare a key/value pair.
+    fn remove_edge(&mut self, mut current: NodeIndex, ordering: Ordering) -> (K, V) {
+        loop {
+            let node = self.node(current);
+            if node.is_leaf() {
+                let node = self.node_mut(current);
+                return match ordering {
+                    Ordering::Less => (node.keys.remove(0), node.values.remove(0)),
+                    _ => {
+                        (
+                            node.keys.pop().expect("a B-tree node must have keys"),
+                            node.values.pop().expect("a B-tree node must have values"),
+                        )
+                    }
+                }
+            }
+            let pos = match ordering {
+                Ordering::Less => 0,
+                _ => node.len(),
+            };
+            current = self.prepare_child(current, pos);
+        }
+    }
+
+    /// Replaces the key/value pair at the given position of the node and
+    /// returns the replaced pair.
+    fn replace_at(
+        &mut self,
+        node: NodeIndex,
+        pos: usize,
+        (key, value): (K, V),
+    ) -> (K, V) {
+        let node = self.node_mut(node);
+        (
+            core::mem::replace(&mut node.keys[pos], key),
+            core::mem::replace(&mut node.values[pos], value),
+        )
+    }
+
+    /// Makes sure that the child at the given position can spare a key/value
+    /// pair before descending into it and returns the index of the node to
+    /// descend into.
+    ///
+    /// If the child has too few keys it either borrows a key/value pair from
+    /// one of its direct siblings or is merged with one of them.
+    fn prepare_child(&mut self, parent: NodeIndex, pos: usize) -> NodeIndex {
+        let node = self.node(parent);
+        let child = node.children[pos];
+        if self.node(child).can_spare() {
+            return child
+        }
+        let left = pos.checked_sub(1).map(|pos| node.children[pos]);
+        let right = node.children.get(pos + 1).copied();
+        if let Some(left) = left.filter(|&left| self.node(left).can_spare()) {
+            self.rotate_right(parent, pos - 1, left, child);
+            return child
+        }
+        if let Some(right) = right.filter(|&right| self.node(right).can_spare()) {
+            self.rotate_left(parent, pos, child, right);
+            return child
+        }
+        if right.is_some() {
+            self.merge_children(parent, pos);
+            child
+        } else {
+            self.merge_children(parent, pos - 1);
+            left.expect("a child without right sibling must have a left sibling")
+        }
+    }
+
+    /// Moves the greatest key/value pair of `left` up into the parent at `pos`
+    /// and the parent's key/value pair at `pos` down into `right`.
+    fn rotate_right(
+        &mut self,
+        parent: NodeIndex,
+        pos: usize,
+        left: NodeIndex,
+        right: NodeIndex,
+    ) {
+        let left = self.node_mut(left);
+        let key = left.keys.pop().expect("a B-tree node must have keys");
+        let value = left.values.pop().expect("a B-tree node must have values");
+        let child = left.children.pop();
+        let (key, value) = self.replace_at(parent, pos, (key, value));
+        let right = self.node_mut(right);
+        right.keys.insert(0, key);
+        right.values.insert(0, value);
+        if let Some(child) = child {
+            right.children.insert(0, child);
+        }
+    }
+
+    /// Moves the smallest key/value pair of `right` up into the parent at `pos`
+    /// and the parent's key/value pair at `pos` down into `left`.
+    fn rotate_left(
+        &mut self,
+        parent: NodeIndex,
+        pos: usize,
+        left: NodeIndex,
+        right: NodeIndex,
+    ) {
+        let right = self.node_mut(right);
+        let key = right.keys.remove(0);
+        let value = right.values.remove(0);
+        let child = if right.is_leaf() {
+            None
+        } else {
+            Some(right.children.remove(0))
+        };
+        let (key, value) = self.replace_at(parent, pos, (key, value));
+        let left = self.node_mut(left);
+        left.keys.push(key);
+        left.values.push(value);
+        if let Some(child) = child {
+            left.children.push(child);
+        }
+    }
+
+    /// Merges the children at `pos` and `pos + 1` of the parent node together
+    /// with the parent's key/value pair at `pos` into the child at `pos`.
+    ///
+    /// The node of the right child is freed.
+    fn merge_children(&mut self, parent: NodeIndex, pos: usize) {
+        let node = self.node_mut(parent);
+        let key = node.keys.remove(pos);
+        let value = node.values.remove(pos);
+        let left = node.children[pos];
+        let right = node.children.remove(pos + 1);
+        let right = self
+            .nodes
+            .take(right)
+            .expect("encountered invalid B-tree node index");
+        let left = self.node_mut(left);
+        left.keys.push(key);
+        left.values.push(value);
+        left.keys.extend(right.keys);
+        left.values.extend(right.values);
+        left.children.extend(right.children);
+        debug_assert!(left.len() <= CAPACITY);
+    }
+
+    /// Frees the root node while it is empty.
+    ///
+    /// This is the only way for the B-tree to shrink in height.
+    fn shrink_root(&mut self) {
+        while let Some(root) = self.header.root {
+            let node = self.node(root);
+            if node.len() > 0 {
+                break
+            }
+            let new_root = node.children.first().copied();
+            self.header.root = new_root;
+            self.nodes.take(root);
+        }
+    }
+
+    /// Removes all key/value pairs from the map.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.header.root = None;
+        self.header.len = 0;
+    }
+}
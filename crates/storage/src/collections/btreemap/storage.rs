@@ -0,0 +1,166 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of ink! storage traits.
+
+use super::{
+    BTreeMap as StorageBTreeMap,
+    Header,
+    Node,
+};
+use crate::{
+    collections::Stash as StorageStash,
+    lazy::Lazy,
+    traits::{
+        forward_clear_packed,
+        forward_pull_packed,
+        forward_push_packed,
+        KeyPtr,
+        PackedLayout,
+        SpreadLayout,
+    },
+};
+use ink_prelude::vec::Vec;
+use ink_primitives::Key;
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        CellLayout,
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        StructLayout,
+    };
+    use scale_info::TypeInfo;
+
+    impl StorageLayout for Header {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            Layout::Cell(CellLayout::new::<Header>(LayoutKey::from(
+                key_ptr.advance_by(1),
+            )))
+        }
+    }
+
+    impl<K, V> StorageLayout for StorageBTreeMap<K, V>
+    where
+        K: TypeInfo + Ord + PackedLayout + 'static,
+        V: TypeInfo + PackedLayout + 'static,
+    {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            Layout::Struct(StructLayout::new(vec![
+                FieldLayout::new(
+                    "header",
+                    <Lazy<Header> as StorageLayout>::layout(key_ptr),
+                ),
+                FieldLayout::new(
+                    "nodes",
+                    <StorageStash<Node<K, V>> as StorageLayout>::layout(key_ptr),
+                ),
+            ]))
+        }
+    }
+};
+
+impl SpreadLayout for Header {
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = false;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl PackedLayout for Header {
+    fn pull_packed(&mut self, _at: &Key) {}
+    fn push_packed(&self, _at: &Key) {}
+    fn clear_packed(&self, _at: &Key) {}
+}
+
+impl<K, V> SpreadLayout for Node<K, V>
+where
+    K: PackedLayout,
+    V: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = <K as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP
+        || <V as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<K, V> PackedLayout for Node<K, V>
+where
+    K: PackedLayout,
+    V: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <Vec<K> as PackedLayout>::pull_packed(&mut self.keys, at);
+        <Vec<V> as PackedLayout>::pull_packed(&mut self.values, at);
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <Vec<K> as PackedLayout>::push_packed(&self.keys, at);
+        <Vec<V> as PackedLayout>::push_packed(&self.values, at);
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <Vec<K> as PackedLayout>::clear_packed(&self.keys, at);
+        <Vec<V> as PackedLayout>::clear_packed(&self.values, at);
+    }
+}
+
+impl<K, V> SpreadLayout for StorageBTreeMap<K, V>
+where
+    K: Ord + PackedLayout,
+    V: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1 + <StorageStash<Node<K, V>> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            header: SpreadLayout::pull_spread(ptr),
+            nodes: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.header, ptr);
+        SpreadLayout::push_spread(&self.nodes, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::clear_spread(&self.header, ptr);
+        SpreadLayout::clear_spread(&self.nodes, ptr);
+    }
+}
@@ -0,0 +1,413 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    BTreeMap as StorageBTreeMap,
+    NodeIndex,
+    CAPACITY,
+    MIN_DEGREE,
+};
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+use std::collections::BTreeMap as StdBTreeMap;
+
+/// Returns always the same `KeyPtr`.
+fn key_ptr() -> KeyPtr {
+    let root_key = Key::from([0x42; 32]);
+    KeyPtr::from(root_key)
+}
+
+/// Pushes a `BTreeMap` instance into the contract storage.
+fn push_map(map: &StorageBTreeMap<u32, i32>) {
+    SpreadLayout::push_spread(map, &mut key_ptr());
+}
+
+/// Pulls a `BTreeMap` instance from the contract storage.
+fn pull_map() -> StorageBTreeMap<u32, i32> {
+    <StorageBTreeMap<u32, i32> as SpreadLayout>::pull_spread(&mut key_ptr())
+}
+
+/// Returns a map that holds the keys `0, 2, 4, ..` up to `2 * (len - 1)`.
+///
+/// The keys are inserted in a scrambled order and each value is the negated key.
+fn even_keys_map(len: u32) -> StorageBTreeMap<u32, i32> {
+    let mut map = StorageBTreeMap::new();
+    for i in 0..len {
+        let key = (i * 7919 % len) * 2;
+        assert_eq!(map.insert(key, -(key as i32)), None);
+    }
+    map
+}
+
+/// Asserts that the B-tree is well formed and holds exactly the given entries.
+fn assert_tree(map: &StorageBTreeMap<u32, i32>, expected: &StdBTreeMap<u32, i32>) {
+    /// Checks the subtree of the node and returns its height.
+    fn check_node(
+        map: &StorageBTreeMap<u32, i32>,
+        index: NodeIndex,
+        is_root: bool,
+        lower: Option<u32>,
+        upper: Option<u32>,
+    ) -> usize {
+        let node = map.node(index);
+        assert!(node.len() <= CAPACITY);
+        assert!(is_root || node.len() >= MIN_DEGREE - 1);
+        assert!(node.len() > 0);
+        assert_eq!(node.keys.len(), node.values.len());
+        assert!(node.keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(lower.map(|lower| lower < node.keys[0]).unwrap_or(true));
+        assert!(upper
+            .map(|upper| node.keys[node.len() - 1] < upper)
+            .unwrap_or(true));
+        if node.is_leaf() {
+            return 1
+        }
+        assert_eq!(node.children.len(), node.len() + 1);
+        let heights = node
+            .children
+            .iter()
+            .enumerate()
+            .map(|(pos, &child)| {
+                let lower = pos.checked_sub(1).map(|pos| node.keys[pos]).or(lower);
+                let upper = node.keys.get(pos).copied().or(upper);
+                check_node(map, child, false, lower, upper)
+            })
+            .collect::<Vec<_>>();
+        assert!(heights.windows(2).all(|pair| pair[0] == pair[1]));
+        heights[0] + 1
+    }
+
+    assert_eq!(map.len() as usize, expected.len());
+    if let Some(root) = map.header.root {
+        check_node(map, root, true, None, None);
+    }
+    assert!(map.iter().eq(expected.iter()));
+}
+
+#[test]
+fn new_works() {
+    // `StorageBTreeMap::new`
+    let map = <StorageBTreeMap<u32, i32>>::new();
+    assert!(map.is_empty());
+    assert_eq!(map.len(), 0);
+    assert!(map.iter().next().is_none());
+    assert_eq!(map.first_key_value(), None);
+    assert_eq!(map.last_key_value(), None);
+    // `StorageBTreeMap::default`
+    let default = <StorageBTreeMap<u32, i32> as Default>::default();
+    assert!(default.is_empty());
+    assert_eq!(default.len(), 0);
+    assert!(default.iter().next().is_none());
+    // `StorageBTreeMap::new` and `StorageBTreeMap::default` should be equal.
+    assert_eq!(map, default);
+}
+
+#[test]
+fn from_iterator_works() {
+    let test_values = [(3, 30), (1, 10), (4, 40), (2, 20)];
+    let map = test_values
+        .iter()
+        .copied()
+        .collect::<StorageBTreeMap<u32, i32>>();
+    assert_eq!(map.len(), 4);
+    assert_eq!(map, {
+        let mut map = <StorageBTreeMap<u32, i32>>::new();
+        for (key, value) in &test_values {
+            assert_eq!(map.insert(*key, *value), None);
+        }
+        map
+    });
+}
+
+#[test]
+fn get_works() {
+    // Empty map.
+    let map = <StorageBTreeMap<u32, i32>>::new();
+    assert_eq!(map.get(&0), None);
+    assert!(!map.contains_key(&0));
+    // Filled map with multiple levels.
+    let mut map = even_keys_map(500);
+    for key in 0..1000 {
+        let expected = if key % 2 == 0 { Some(-(key as i32)) } else { None };
+        assert_eq!(map.get(&key).copied(), expected);
+        assert_eq!(map.contains_key(&key), expected.is_some());
+    }
+    // `get_mut` and indexing.
+    *map.get_mut(&42).unwrap() = 42;
+    map[&44] = 44;
+    assert_eq!(map[&42], 42);
+    assert_eq!(map[&44], 44);
+    assert_eq!(map.get_mut(&43), None);
+}
+
+#[test]
+fn insert_works() {
+    let mut map = <StorageBTreeMap<u32, i32>>::new();
+    let mut expected = StdBTreeMap::new();
+    // Insert in descending order to split nodes at their front.
+    for key in (0..300).rev() {
+        assert_eq!(map.insert(key, key as i32), None);
+        expected.insert(key, key as i32);
+    }
+    assert_tree(&map, &expected);
+    // Overwriting keeps the length and returns the old value.
+    for key in (0..300).step_by(3) {
+        assert_eq!(map.insert(key, -1), Some(key as i32));
+        expected.insert(key, -1);
+    }
+    assert_tree(&map, &expected);
+}
+
+#[test]
+fn take_works() {
+    let mut map = even_keys_map(500);
+    let mut expected = map
+        .iter()
+        .map(|(&key, &value)| (key, value))
+        .collect::<StdBTreeMap<_, _>>();
+    // Taking a missing key does not change the map.
+    assert_eq!(map.take(&1), None);
+    assert_eq!(map.remove_entry(&1001), None);
+    assert_tree(&map, &expected);
+    // Take from the front, the middle and the back of the map.
+    for key in (0..1000).step_by(6).chain((0..1000).rev().step_by(4)) {
+        assert_eq!(map.take(&key), expected.remove(&key));
+        assert_tree(&map, &expected);
+    }
+    // Take all remaining keys.
+    for key in 0..1000 {
+        assert_eq!(map.take(&key), expected.remove(&key));
+    }
+    assert_tree(&map, &expected);
+    assert!(map.is_empty());
+    assert_eq!(map.header.root, None);
+    assert_eq!(map.nodes.len(), 0);
+}
+
+#[test]
+fn first_and_last_key_value_works() {
+    let mut map = even_keys_map(100);
+    assert_eq!(map.first_key_value(), Some((&0, &0)));
+    assert_eq!(map.last_key_value(), Some((&198, &-198)));
+    map.take(&0);
+    map.take(&198);
+    assert_eq!(map.first_key_value(), Some((&2, &-2)));
+    assert_eq!(map.last_key_value(), Some((&196, &-196)));
+}
+
+#[test]
+fn iter_works() {
+    let map = even_keys_map(400);
+    let mut iter = map.iter();
+    assert_eq!(iter.len(), 400);
+    assert_eq!(iter.next(), Some((&0, &0)));
+    assert_eq!(iter.next(), Some((&2, &-2)));
+    assert_eq!(iter.len(), 398);
+    assert_eq!(iter.count(), 398);
+    assert!(map
+        .iter()
+        .map(|(&key, _)| key)
+        .eq((0..800).step_by(2)));
+}
+
+#[test]
+fn range_works() {
+    use core::ops::Bound::{
+        self,
+        Excluded,
+        Included,
+        Unbounded,
+    };
+    let map = even_keys_map(300);
+    let expected = map
+        .iter()
+        .map(|(&key, &value)| (key, value))
+        .collect::<StdBTreeMap<_, _>>();
+    let bounds = |key: u32| [Included(key), Excluded(key), Unbounded];
+    for start_key in (0..620).step_by(7) {
+        for end_key in (start_key..620).step_by(11) {
+            for &start in &bounds(start_key) {
+                for &end in &bounds(end_key) {
+                    if let (Excluded(_), Excluded(_)) = (start, end) {
+                        if start_key == end_key {
+                            continue
+                        }
+                    }
+                    let range: (Bound<u32>, Bound<u32>) = (start, end);
+                    assert!(
+                        map.range(range).eq(expected.range(range)),
+                        "range {:?} differs",
+                        range,
+                    );
+                }
+            }
+        }
+    }
+    // Ranges within an empty map are empty.
+    let empty = <StorageBTreeMap<u32, i32>>::new();
+    assert_eq!(empty.range(1..10).next(), None);
+}
+
+#[test]
+#[should_panic(expected = "range start is greater than range end in storage B-tree map")]
+fn range_with_start_greater_than_end_fails() {
+    let map = even_keys_map(10);
+    let _ = map.range(5..4);
+}
+
+#[test]
+#[should_panic(
+    expected = "range start and end are equal and excluded in storage B-tree map"
+)]
+fn range_with_equal_excluded_bounds_fails() {
+    use core::ops::Bound::Excluded;
+    let map = even_keys_map(10);
+    let _ = map.range((Excluded(4), Excluded(4)));
+}
+
+#[test]
+fn clear_works() {
+    let mut map = even_keys_map(100);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().next(), None);
+    assert_eq!(map.get(&0), None);
+    assert_eq!(map.nodes.len(), 0);
+    // The map is still usable after clearing it.
+    assert_eq!(map.insert(1, 1), None);
+    assert_eq!(map.get(&1), Some(&1));
+}
+
+#[test]
+fn randomized_operations_work() {
+    let mut map = <StorageBTreeMap<u32, i32>>::new();
+    let mut expected = StdBTreeMap::new();
+    let mut seed = 0x2545_f491_u32;
+    let mut max_nodes = 0;
+    for round in 0..4000 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let key = seed % 512;
+        // Insert more often during the first half and remove more often afterwards.
+        let insert = (seed >> 16) % 8 < if round < 2000 { 5 } else { 3 };
+        if insert {
+            assert_eq!(map.insert(key, round), expected.insert(key, round));
+        } else {
+            assert_eq!(map.take(&key), expected.remove(&key));
+        }
+        max_nodes = max_nodes.max(map.nodes.len());
+        if round % 100 == 0 {
+            assert_tree(&map, &expected);
+        }
+    }
+    assert_tree(&map, &expected);
+    // Freed nodes are reused so that the number of node slots stays bounded
+    // by the peak number of live nodes.
+    assert_eq!(map.nodes.capacity(), max_nodes);
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let map1 = even_keys_map(300);
+        push_map(&map1);
+        // Load the pushed storage B-tree map into another instance and check
+        // that both instances are equal:
+        let mut map2 = pull_map();
+        assert_eq!(map1, map2);
+        // Mutate the loaded instance and check that the changes persist:
+        let mut expected = map1
+            .iter()
+            .map(|(&key, &value)| (key, value))
+            .collect::<StdBTreeMap<_, _>>();
+        for key in (0..600).step_by(3) {
+            if key % 2 == 0 {
+                assert_eq!(map2.take(&key), expected.remove(&key));
+            } else {
+                assert_eq!(map2.insert(key, 1), expected.insert(key, 1));
+            }
+        }
+        push_map(&map2);
+        let map3 = pull_map();
+        assert_tree(&map3, &expected);
+        // Avoid clearing the storage when dropping the instances:
+        core::mem::forget((map1, map2, map3));
+        Ok(())
+    })
+}
+
+#[test]
+fn spread_layout_push_pull_only_touches_the_path() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let map1 = even_keys_map(1000);
+        push_map(&map1);
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let mut map2 = pull_map();
+        let (base_reads, base_writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        assert_eq!(map2.get(&500), Some(&-500));
+        let (reads, _) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        // A lookup loads the header and at most one node per level.
+        // Every load consists of two storage reads.
+        let height = {
+            let mut height = 1;
+            let mut current = map2.header.root.unwrap();
+            while !map2.node(current).is_leaf() {
+                current = map2.node(current).children[0];
+                height += 1;
+            }
+            height
+        };
+        assert!(reads - base_reads <= 2 * (1 + height));
+        // Updating a value only rewrites the node containing it besides the
+        // header of the underlying storage stash which is always written.
+        *map2.get_mut(&500).unwrap() = 0;
+        push_map(&map2);
+        let (_, writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        assert_eq!(writes - base_writes, 2);
+        core::mem::forget((map1, map2));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "storage entry was empty")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let map1 = even_keys_map(100);
+        push_map(&map1);
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `map1` and check whether
+        // loading another instance from this storage will panic since the
+        // map's header cannot be read:
+        SpreadLayout::clear_spread(&map1, &mut key_ptr());
+        let map2 = pull_map();
+        let _ = map2.len();
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,244 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage double-ended queue that allows to efficiently push and pop
+//! elements at both of its ends.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::iter::{
+    Iter,
+    IterMut,
+};
+use crate::{
+    lazy::{
+        Lazy,
+        LazyIndexMap,
+    },
+    traits::PackedLayout,
+};
+
+/// A double-ended queue operating on the contract storage.
+///
+/// # Note
+///
+/// The elements are stored in a ring that spans the whole `u32` index space
+/// of the underlying storage cells. The position of the front element moves
+/// with every push or pop at the front and wraps around at the ends of the
+/// index space. Therefore the deque never has to relocate its elements.
+///
+/// Allows to store up to `2^32 - 1` elements.
+#[derive(Debug)]
+pub struct Deque<T>
+where
+    T: PackedLayout,
+{
+    /// The position of the front element and the length of the deque.
+    header: Lazy<Header>,
+    /// The synchronized cells to operate on the contract storage.
+    elems: LazyIndexMap<T>,
+}
+
+/// Stores general commonly required information about the storage deque.
+#[derive(Debug, Default, Copy, Clone, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header {
+    /// The storage cell index of the front element.
+    head: u32,
+    /// The number of elements stored in the deque.
+    len: u32,
+}
+
+impl<T> Deque<T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new empty storage deque.
+    pub fn new() -> Self {
+        Self {
+            header: Lazy::new(Header::default()),
+            elems: LazyIndexMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> u32 {
+        self.header.len
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the storage cell index of the element at the given position.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    fn cell_index(&self, index: u32) -> Option<u32> {
+        if index < self.len() {
+            return Some(self.header.head.wrapping_add(index))
+        }
+        None
+    }
+
+    /// Clears the underlying storage cells of the storage deque.
+    ///
+    /// # Note
+    ///
+    /// This completely invalidates the storage deque's invariances about
+    /// the contents of its associated storage region.
+    ///
+    /// This API is used for the `Drop` implementation of [`Deque`] as well as
+    /// for the [`SpreadLayout::clear_spread`] trait implementation.
+    fn clear_cells(&self) {
+        if self.elems.key().is_none() {
+            // We won't clear any storage if we are in lazy state since there
+            // probably has not been any state written to storage, yet.
+            return
+        }
+        for index in 0..self.len() {
+            self.elems.clear_packed_at(self.header.head.wrapping_add(index));
+        }
+    }
+
+    /// Returns an iterator yielding shared references to all elements of the
+    /// deque from front to back.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage deques.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter(&self) -> Iter<T> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator yielding exclusive references to all elements of the
+    /// deque from front to back.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage deques.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter_mut(&mut self) -> IterMut<T> {
+        IterMut::new(self)
+    }
+
+    /// Returns a shared reference to the indexed element.
+    ///
+    /// The element at index `0` is the front of the deque.
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get(&self, index: u32) -> Option<&T> {
+        self.cell_index(index)
+            .and_then(|index| self.elems.get(index))
+    }
+
+    /// Returns an exclusive reference to the indexed element.
+    ///
+    /// The element at index `0` is the front of the deque.
+    /// Returns `None` if `index` is out of bounds.
+    pub fn get_mut(&mut self, index: u32) -> Option<&mut T> {
+        self.cell_index(index)
+            .and_then(move |index| self.elems.get_mut(index))
+    }
+
+    /// Returns a shared reference to the front element if any.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns an exclusive reference to the front element if any.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns a shared reference to the back element if any.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Returns an exclusive reference to the back element if any.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len().checked_sub(1)?)
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// # Panics
+    ///
+    /// If the deque already stores `2^32 - 1` elements.
+    pub fn push_back(&mut self, value: T) {
+        assert!(
+            self.len() < core::u32::MAX,
+            "cannot push more elements into the storage deque"
+        );
+        let back = self.header.head.wrapping_add(self.len());
+        self.header.len += 1;
+        self.elems.put(back, Some(value));
+    }
+
+    /// Prepends an element to the front of the deque.
+    ///
+    /// # Panics
+    ///
+    /// If the deque already stores `2^32 - 1` elements.
+    pub fn push_front(&mut self, value: T) {
+        assert!(
+            self.len() < core::u32::MAX,
+            "cannot push more elements into the storage deque"
+        );
+        let front = self.header.head.wrapping_sub(1);
+        self.header.head = front;
+        self.header.len += 1;
+        self.elems.put(front, Some(value));
+    }
+
+    /// Removes the front element from the deque and returns it.
+    ///
+    /// Returns `None` if the deque is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let front = self.cell_index(0)?;
+        self.header.head = front.wrapping_add(1);
+        self.header.len -= 1;
+        self.elems.put_get(front, None)
+    }
+
+    /// Removes the back element from the deque and returns it.
+    ///
+    /// Returns `None` if the deque is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let back = self.cell_index(self.len().checked_sub(1)?)?;
+        self.header.len -= 1;
+        self.elems.put_get(back, None)
+    }
+
+    /// Removes all elements from the deque.
+    ///
+    /// # Note
+    ///
+    /// Use this API with caution since it traverses the deque in order to
+    /// remove all of its elements.
+    pub fn clear(&mut self) {
+        for index in 0..self.len() {
+            self.elems.put(self.header.head.wrapping_add(index), None);
+        }
+        self.header.len = 0;
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Deque as StorageDeque;
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+use std::collections::VecDeque as StdVecDeque;
+
+/// Creates a storage deque from the given slice.
+fn deque_from_slice(slice: &[u8]) -> StorageDeque<u8> {
+    slice.iter().copied().collect::<StorageDeque<u8>>()
+}
+
+/// Asserts that the storage deque holds exactly the given elements in order.
+fn assert_eq_std(deque: &StorageDeque<u8>, expected: &StdVecDeque<u8>) {
+    assert_eq!(deque.len() as usize, expected.len());
+    assert_eq!(deque.is_empty(), expected.is_empty());
+    assert_eq!(deque.front(), expected.front());
+    assert_eq!(deque.back(), expected.back());
+    assert!(deque.iter().eq(expected.iter()));
+    assert!(deque.iter().rev().eq(expected.iter().rev()));
+}
+
+#[test]
+fn new_default_works() {
+    let deque = <StorageDeque<u8>>::new();
+    assert!(deque.is_empty());
+    assert_eq!(deque.len(), 0);
+    assert_eq!(deque.get(0), None);
+    assert_eq!(deque.front(), None);
+    assert_eq!(deque.back(), None);
+    assert!(deque.iter().next().is_none());
+    let default = <StorageDeque<u8> as Default>::default();
+    assert!(default.is_empty());
+    assert_eq!(deque, default);
+}
+
+#[test]
+fn push_back_and_pop_front_works() {
+    let mut deque = <StorageDeque<u8>>::new();
+    deque.push_back(b'a');
+    deque.push_back(b'b');
+    deque.push_back(b'c');
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.front(), Some(&b'a'));
+    assert_eq!(deque.back(), Some(&b'c'));
+    assert_eq!(deque.pop_front(), Some(b'a'));
+    assert_eq!(deque.pop_front(), Some(b'b'));
+    assert_eq!(deque.pop_front(), Some(b'c'));
+    assert_eq!(deque.pop_front(), None);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn push_front_and_pop_back_works() {
+    // `push_front` on an empty deque wraps around the storage index space.
+    let mut deque = <StorageDeque<u8>>::new();
+    deque.push_front(b'a');
+    deque.push_front(b'b');
+    deque.push_front(b'c');
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.header.head, u32::MAX - 2);
+    assert!(deque.iter().eq([b'c', b'b', b'a'].iter()));
+    assert_eq!(deque.pop_back(), Some(b'a'));
+    assert_eq!(deque.pop_back(), Some(b'b'));
+    assert_eq!(deque.pop_back(), Some(b'c'));
+    assert_eq!(deque.pop_back(), None);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn get_works() {
+    let mut deque = deque_from_slice(&[b'b', b'c']);
+    deque.push_front(b'a');
+    assert_eq!(deque.get(0), Some(&b'a'));
+    assert_eq!(deque.get(1), Some(&b'b'));
+    assert_eq!(deque.get(2), Some(&b'c'));
+    assert_eq!(deque.get(3), None);
+    assert_eq!(deque[2], b'c');
+    *deque.get_mut(0).unwrap() = b'x';
+    deque[1] = b'y';
+    *deque.back_mut().unwrap() = b'z';
+    assert_eq!(deque.get_mut(3), None);
+    assert!(deque.iter().eq([b'x', b'y', b'z'].iter()));
+    for elem in deque.iter_mut() {
+        *elem += 1;
+    }
+    assert!(deque.iter().eq([b'y', b'z', b'{'].iter()));
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+fn index_out_of_bounds_fails() {
+    let deque = deque_from_slice(&[b'a', b'b', b'c']);
+    let _ = deque[3];
+}
+
+#[test]
+fn clear_works() {
+    let mut deque = deque_from_slice(&[b'a', b'b', b'c']);
+    deque.push_front(b'z');
+    deque.clear();
+    assert!(deque.is_empty());
+    assert_eq!(deque.front(), None);
+    assert!(deque.iter().next().is_none());
+    // The deque is still usable after clearing it.
+    deque.push_front(b'a');
+    assert_eq!(deque.back(), Some(&b'a'));
+}
+
+#[test]
+fn interleaved_operations_work() {
+    let mut deque = <StorageDeque<u8>>::new();
+    let mut expected = StdVecDeque::new();
+    let mut seed = 0x2545_f491_u32;
+    for round in 0..2000 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let value = (round % 256) as u8;
+        match seed % 5 {
+            0 => {
+                deque.push_back(value);
+                expected.push_back(value);
+            }
+            1 => {
+                deque.push_front(value);
+                expected.push_front(value);
+            }
+            2 => assert_eq!(deque.pop_front(), expected.pop_front()),
+            3 => assert_eq!(deque.pop_back(), expected.pop_back()),
+            _ => {
+                let index = seed % (expected.len() as u32 + 1);
+                assert_eq!(deque.get(index), expected.get(index as usize));
+            }
+        }
+        if round % 50 == 0 {
+            assert_eq_std(&deque, &expected);
+        }
+    }
+    assert_eq_std(&deque, &expected);
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut deque1 = deque_from_slice(&[b'b', b'c', b'd']);
+        deque1.push_front(b'a');
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&deque1, &mut KeyPtr::from(root_key));
+        // Load the pushed storage deque into another instance and check that
+        // both instances are equal:
+        let mut deque2 =
+            <StorageDeque<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(deque1, deque2);
+        // Keep operating on the loaded instance across another push and pull:
+        let mut expected = deque2.iter().copied().collect::<StdVecDeque<_>>();
+        assert_eq!(deque2.pop_front(), expected.pop_front());
+        assert_eq!(deque2.pop_front(), expected.pop_front());
+        deque2.push_front(b'x');
+        expected.push_front(b'x');
+        deque2.push_back(b'y');
+        expected.push_back(b'y');
+        SpreadLayout::push_spread(&deque2, &mut KeyPtr::from(root_key));
+        let deque3 =
+            <StorageDeque<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq_std(&deque3, &expected);
+        // Avoid clearing the storage when dropping the instances:
+        core::mem::forget((deque1, deque2, deque3));
+        Ok(())
+    })
+}
+
+#[test]
+fn popped_cells_are_cleared() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut deque1 = deque_from_slice(&[b'b', b'c']);
+        deque1.push_front(b'a');
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&deque1, &mut KeyPtr::from(root_key));
+        // The elements are stored right after the header field.
+        let elem_key = |index: u32| root_key + 1 + index as u64;
+        assert_eq!(
+            ink_env::get_contract_storage::<u8>(&elem_key(u32::MAX)),
+            Ok(Some(b'a'))
+        );
+        assert_eq!(
+            ink_env::get_contract_storage::<u8>(&elem_key(1)),
+            Ok(Some(b'c'))
+        );
+        let mut deque2 =
+            <StorageDeque<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(deque2.pop_front(), Some(b'a'));
+        assert_eq!(deque2.pop_back(), Some(b'c'));
+        SpreadLayout::push_spread(&deque2, &mut KeyPtr::from(root_key));
+        assert_eq!(
+            ink_env::get_contract_storage::<u8>(&elem_key(u32::MAX)),
+            Ok(None)
+        );
+        assert_eq!(ink_env::get_contract_storage::<u8>(&elem_key(1)), Ok(None));
+        assert_eq!(
+            ink_env::get_contract_storage::<u8>(&elem_key(0)),
+            Ok(Some(b'b'))
+        );
+        core::mem::forget((deque1, deque2));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "encountered empty storage cell")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let deque1 = deque_from_slice(&[b'a', b'b', b'c', b'd']);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&deque1, &mut KeyPtr::from(root_key));
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `deque1` and check whether
+        // loading another instance from this storage will panic since the
+        // deque's header cannot be read:
+        SpreadLayout::clear_spread(&deque1, &mut KeyPtr::from(root_key));
+        let _ =
+            <StorageDeque<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        Ok(())
+    })
+    .unwrap()
+}
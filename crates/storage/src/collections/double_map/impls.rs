@@ -0,0 +1,84 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::DoubleMap as StorageDoubleMap;
+use crate::traits::PackedLayout;
+use core::iter::FromIterator;
+use ink_env::hash::{
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+impl<K1, K2, V, H> Drop for StorageDoubleMap<K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn drop(&mut self) {
+        self.clear_cells();
+    }
+}
+
+impl<K1, K2, V, H> Default for StorageDoubleMap<K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn default() -> Self {
+        StorageDoubleMap::new()
+    }
+}
+
+impl<K1, K2, V, H> Extend<(K1, K2, V)> for StorageDoubleMap<K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K1, K2, V)>,
+    {
+        for (k1, k2, value) in iter {
+            self.insert(k1, k2, value);
+        }
+    }
+}
+
+impl<K1, K2, V, H> FromIterator<(K1, K2, V)> for StorageDoubleMap<K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K1, K2, V)>,
+    {
+        let mut map = StorageDoubleMap::new();
+        map.extend(iter);
+        map
+    }
+}
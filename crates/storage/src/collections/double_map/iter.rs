@@ -0,0 +1,141 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Entry;
+use crate::{
+    collections::DoubleMap as StorageDoubleMap,
+    lazy::LazyHashMap,
+    traits::PackedLayout,
+};
+use ink_env::hash::{
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+/// An iterator over shared references to the second keys and values stored
+/// under a single first key of a storage double map.
+#[derive(Debug, Clone)]
+pub struct PrefixIter<'a, K1, K2, V, H>
+where
+    K1: PackedLayout,
+    K2: PackedLayout,
+{
+    /// The lazy hash map to query the entries.
+    entries: &'a LazyHashMap<(K1, K2), Entry<K2, V>, H>,
+    /// The first key of the yielded values.
+    k1: K1,
+    /// The second key of the next value to yield from the front.
+    front: Option<&'a K2>,
+    /// The second key of the next value to yield from the back.
+    back: Option<&'a K2>,
+    /// The number of yet to be yielded values.
+    remaining: u32,
+}
+
+impl<'a, K1, K2, V, H> PrefixIter<'a, K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Creates a new iterator over the values under the given first key.
+    pub(crate) fn new(
+        double_map: &'a StorageDoubleMap<K1, K2, V, H>,
+        k1: &K1,
+    ) -> Self {
+        let prefix = double_map.prefixes.get(k1);
+        Self {
+            entries: &double_map.entries,
+            k1: k1.clone(),
+            front: prefix.map(|prefix| &prefix.first),
+            back: prefix.map(|prefix| &prefix.last),
+            remaining: prefix.map(|prefix| prefix.len).unwrap_or(0),
+        }
+    }
+
+    /// Queries the entry for the given second key.
+    ///
+    /// # Panics
+    ///
+    /// If the keys refer to an invalid entry.
+    fn query_entry(&self, k2: &'a K2) -> &'a Entry<K2, V> {
+        self.entries
+            .get(&(self.k1.clone(), k2.clone()))
+            .expect("a linked value must always refer to an existing entry")
+    }
+}
+
+impl<'a, K1, K2, V, H> Iterator for PrefixIter<'a, K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    type Item = (&'a K2, &'a V);
+
+    fn count(self) -> usize {
+        self.remaining as usize
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let k2 = self.front?;
+        let entry = self.query_entry(k2);
+        self.front = entry.next.as_ref();
+        self.remaining -= 1;
+        Some((k2, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K1, K2, V, H> ExactSizeIterator for PrefixIter<'a, K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+}
+
+impl<'a, K1, K2, V, H> DoubleEndedIterator for PrefixIter<'a, K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let k2 = self.back?;
+        let entry = self.query_entry(k2);
+        self.back = entry.prev.as_ref();
+        self.remaining -= 1;
+        Some((k2, &entry.value))
+    }
+}
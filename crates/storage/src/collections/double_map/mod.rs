@@ -0,0 +1,370 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage map that associates values with pairs of keys and allows to
+//! enumerate and remove all values sharing the same first key.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::iter::PrefixIter;
+use crate::{
+    lazy::{
+        Lazy,
+        LazyHashMap,
+    },
+    traits::PackedLayout,
+};
+use ink_env::hash::{
+    Blake2x256,
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+/// A map operating on the contract storage that is keyed by two keys.
+///
+/// # Note
+///
+/// The storage cell of a value is derived from hashing the encoding of the
+/// first key followed by the encoding of the second key.
+///
+/// All values that share the same first key, called a prefix, are linked to
+/// each other through their storage cells. This allows to enumerate and
+/// remove all values under a prefix without knowing their second keys, for
+/// example in order to drop all allowances granted by a single owner.
+/// The prefixes themselves are linked to each other as well so that the whole
+/// map can be cleared.
+#[derive(Debug)]
+pub struct DoubleMap<K1, K2, V, H = Blake2x256>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// The number of values and the first and last prefix of the map.
+    header: Lazy<Header<K1>>,
+    /// The prefixes of the map.
+    prefixes: LazyHashMap<K1, Prefix<K1, K2>, H>,
+    /// The values of the map together with their links.
+    entries: LazyHashMap<(K1, K2), Entry<K2, V>, H>,
+}
+
+/// Stores general commonly required information about the storage double map.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header<K1> {
+    /// The number of values stored in the map.
+    len: u32,
+    /// The first prefix if any.
+    first: Option<K1>,
+    /// The last prefix if any.
+    last: Option<K1>,
+}
+
+/// A prefix of the storage double map, i.e. a first key with values.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Prefix<K1, K2> {
+    /// The number of values stored under the prefix.
+    len: u32,
+    /// The previous prefix if any.
+    prev: Option<K1>,
+    /// The next prefix if any.
+    next: Option<K1>,
+    /// The second key of the first value under the prefix.
+    first: K2,
+    /// The second key of the last value under the prefix.
+    last: K2,
+}
+
+/// A value of the storage double map.
+///
+/// Stores the value as well as the second keys of its neighbours under the
+/// same prefix.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Entry<K2, V> {
+    /// The value stored in this entry.
+    value: V,
+    /// The second key of the previous value under the same prefix if any.
+    prev: Option<K2>,
+    /// The second key of the next value under the same prefix if any.
+    next: Option<K2>,
+}
+
+impl<K1, K2, V, H> DoubleMap<K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Creates a new empty storage double map.
+    pub fn new() -> Self {
+        Self {
+            header: Lazy::new(Header {
+                len: 0,
+                first: None,
+                last: None,
+            }),
+            prefixes: LazyHashMap::new(),
+            entries: LazyHashMap::new(),
+        }
+    }
+
+    /// Returns the number of values stored in the map.
+    pub fn len(&self) -> u32 {
+        self.header.len
+    }
+
+    /// Returns `true` if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of values stored under the given first key.
+    pub fn len_prefix(&self, k1: &K1) -> u32 {
+        self.prefixes.get(k1).map(|prefix| prefix.len).unwrap_or(0)
+    }
+
+    /// Returns an iterator yielding shared references to all second keys and
+    /// values stored under the given first key in insertion order.
+    ///
+    /// # Note
+    ///
+    /// - Avoid unbounded iteration over big prefixes.
+    /// - Prefer using methods like `Iterator::take` in order to limit the number
+    ///   of yielded elements.
+    pub fn iter_prefix(&self, k1: &K1) -> PrefixIter<K1, K2, V, H> {
+        PrefixIter::new(self, k1)
+    }
+
+    /// Returns the prefix of the given first key.
+    ///
+    /// # Panics
+    ///
+    /// If there is no prefix for the first key.
+    fn prefix_mut(&mut self, k1: &K1) -> &mut Prefix<K1, K2> {
+        self.prefixes
+            .get_mut(k1)
+            .expect("a linked prefix must always refer to an existing entry")
+    }
+
+    /// Returns the entry of the given pair of keys.
+    ///
+    /// # Panics
+    ///
+    /// If there is no entry for the pair of keys.
+    fn entry_mut(&mut self, k1: &K1, k2: &K2) -> &mut Entry<K2, V> {
+        self.entries
+            .get_mut(&(k1.clone(), k2.clone()))
+            .expect("a linked value must always refer to an existing entry")
+    }
+
+    fn clear_cells(&self) {
+        if self.entries.key().is_none() {
+            // We won't clear any storage if we are in lazy state since there
+            // probably has not been any state written to storage, yet.
+            return
+        }
+        let mut next_prefix = self.header.first.as_ref();
+        while let Some(k1) = next_prefix {
+            for (k2, _) in self.iter_prefix(k1) {
+                self.entries.clear_packed_at(&(k1.clone(), k2.clone()));
+            }
+            next_prefix = self
+                .prefixes
+                .get(k1)
+                .expect("a linked prefix must always refer to an existing entry")
+                .next
+                .as_ref();
+            self.prefixes.clear_packed_at(k1);
+        }
+    }
+}
+
+impl<K1, K2, V, H> DoubleMap<K1, K2, V, H>
+where
+    K1: Ord + Eq + Clone + PackedLayout,
+    K2: Ord + Eq + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Inserts a value into the map under the given pair of keys.
+    ///
+    /// Returns the previous value associated with the same pair of keys if any.
+    /// If the map did not have this pair of keys present, `None` is returned.
+    pub fn insert(&mut self, k1: K1, k2: K2, new_value: V) -> Option<V> {
+        let keys = (k1, k2);
+        if let Some(occupied) = self.entries.get_mut(&keys) {
+            let old_value = core::mem::replace(&mut occupied.value, new_value);
+            return Some(old_value)
+        }
+        let (k1, k2) = keys;
+        let prev = match self.prefixes.get_mut(&k1) {
+            Some(prefix) => {
+                prefix.len += 1;
+                Some(core::mem::replace(&mut prefix.last, k2.clone()))
+            }
+            None => {
+                self.push_prefix(k1.clone(), k2.clone());
+                None
+            }
+        };
+        if let Some(prev) = &prev {
+            self.entry_mut(&k1, prev).next = Some(k2.clone());
+        }
+        self.entries.put(
+            (k1, k2),
+            Some(Entry {
+                value: new_value,
+                prev,
+                next: None,
+            }),
+        );
+        self.header.len += 1;
+        None
+    }
+
+    /// Appends a new prefix holding a single value with the given second key.
+    fn push_prefix(&mut self, k1: K1, k2: K2) {
+        let prev = self.header.last.replace(k1.clone());
+        match &prev {
+            Some(prev) => self.prefix_mut(prev).next = Some(k1.clone()),
+            None => self.header.first = Some(k1.clone()),
+        }
+        self.prefixes.put(
+            k1,
+            Some(Prefix {
+                len: 1,
+                prev,
+                next: None,
+                first: k2.clone(),
+                last: k2,
+            }),
+        );
+    }
+
+    /// Removes the prefix of the given first key and returns it if any.
+    ///
+    /// # Note
+    ///
+    /// This does not remove the values stored under the prefix.
+    fn unlink_prefix(&mut self, k1: &K1) -> Option<Prefix<K1, K2>> {
+        let prefix = self.prefixes.put_get(k1, None)?;
+        match &prefix.prev {
+            Some(prev) => self.prefix_mut(prev).next = prefix.next.clone(),
+            None => self.header.first = prefix.next.clone(),
+        }
+        match &prefix.next {
+            Some(next) => self.prefix_mut(next).prev = prefix.prev.clone(),
+            None => self.header.last = prefix.prev.clone(),
+        }
+        Some(prefix)
+    }
+
+    /// Removes the value associated with the given pair of keys from the map.
+    ///
+    /// Returns the removed value if any.
+    pub fn take(&mut self, k1: &K1, k2: &K2) -> Option<V> {
+        let entry = self.entries.put_get(&(k1.clone(), k2.clone()), None)?;
+        self.header.len -= 1;
+        if self.len_prefix(k1) == 1 {
+            self.unlink_prefix(k1);
+            return Some(entry.value)
+        }
+        match &entry.prev {
+            Some(prev) => self.entry_mut(k1, prev).next = entry.next.clone(),
+            None => {
+                self.prefix_mut(k1).first =
+                    entry.next.clone().expect("prefix must have a next value")
+            }
+        }
+        match &entry.next {
+            Some(next) => self.entry_mut(k1, next).prev = entry.prev,
+            None => {
+                self.prefix_mut(k1).last =
+                    entry.prev.expect("prefix must have a previous value")
+            }
+        }
+        self.prefix_mut(k1).len -= 1;
+        Some(entry.value)
+    }
+
+    /// Removes all values stored under the given first key.
+    ///
+    /// Returns the number of removed values.
+    ///
+    /// # Note
+    ///
+    /// This loads every value under the prefix from the contract storage.
+    /// Values stored under other first keys are not touched.
+    pub fn remove_prefix(&mut self, k1: &K1) -> u32 {
+        let prefix = match self.unlink_prefix(k1) {
+            Some(prefix) => prefix,
+            None => return 0,
+        };
+        let mut next = Some(prefix.first);
+        while let Some(k2) = next {
+            next = self
+                .entries
+                .put_get(&(k1.clone(), k2), None)
+                .expect("a linked value must always refer to an existing entry")
+                .next;
+        }
+        self.header.len -= prefix.len;
+        prefix.len
+    }
+
+    /// Removes all values from the map.
+    ///
+    /// # Note
+    ///
+    /// This loads every value from the contract storage.
+    pub fn clear(&mut self) {
+        while let Some(k1) = self.header.first.clone() {
+            self.remove_prefix(&k1);
+        }
+    }
+
+    /// Returns a shared reference to the value associated with the given pair
+    /// of keys.
+    pub fn get(&self, k1: &K1, k2: &K2) -> Option<&V> {
+        self.entries
+            .get(&(k1.clone(), k2.clone()))
+            .map(|entry| &entry.value)
+    }
+
+    /// Returns an exclusive reference to the value associated with the given
+    /// pair of keys.
+    pub fn get_mut(&mut self, k1: &K1, k2: &K2) -> Option<&mut V> {
+        self.entries
+            .get_mut(&(k1.clone(), k2.clone()))
+            .map(|entry| &mut entry.value)
+    }
+
+    /// Returns `true` if there is a value associated with the given pair of keys.
+    pub fn contains_key(&self, k1: &K1, k2: &K2) -> bool {
+        self.get(k1, k2).is_some()
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of ink! storage traits.
+
+use super::{
+    DoubleMap as StorageDoubleMap,
+    Entry,
+    Header,
+    Prefix,
+};
+use crate::{
+    lazy::LazyHashMap,
+    traits::{
+        forward_clear_packed,
+        forward_pull_packed,
+        forward_push_packed,
+        KeyPtr,
+        PackedLayout,
+        SpreadLayout,
+    },
+};
+use ink_env::hash::{
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::{
+        lazy::Lazy,
+        traits::{
+            LayoutCryptoHasher,
+            StorageLayout,
+        },
+    };
+    use ink_metadata::layout::{
+        CellLayout,
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        StructLayout,
+    };
+    use scale_info::TypeInfo;
+
+    impl<K1> StorageLayout for Header<K1>
+    where
+        K1: TypeInfo + 'static,
+    {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            Layout::Cell(CellLayout::new::<Header<K1>>(LayoutKey::from(
+                key_ptr.advance_by(1),
+            )))
+        }
+    }
+
+    impl<K1, K2, V, H> StorageLayout for StorageDoubleMap<K1, K2, V, H>
+    where
+        K1: TypeInfo + Ord + Clone + PackedLayout + 'static,
+        K2: TypeInfo + Ord + Clone + PackedLayout + 'static,
+        V: TypeInfo + PackedLayout + 'static,
+        H: LayoutCryptoHasher + CryptoHash,
+        Key: From<<H as HashOutput>::Type>,
+    {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            Layout::Struct(StructLayout::new(vec![
+                FieldLayout::new(
+                    "header",
+                    <Lazy<Header<K1>> as StorageLayout>::layout(key_ptr),
+                ),
+                FieldLayout::new(
+                    "prefixes",
+                    <LazyHashMap<K1, Prefix<K1, K2>, H> as StorageLayout>::layout(
+                        key_ptr,
+                    ),
+                ),
+                FieldLayout::new(
+                    "entries",
+                    <LazyHashMap<(K1, K2), Entry<K2, V>, H> as StorageLayout>::layout(
+                        key_ptr,
+                    ),
+                ),
+            ]))
+        }
+    }
+};
+
+impl<K1> SpreadLayout for Header<K1>
+where
+    K1: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = <K1 as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<K1> PackedLayout for Header<K1>
+where
+    K1: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <Option<K1> as PackedLayout>::pull_packed(&mut self.first, at);
+        <Option<K1> as PackedLayout>::pull_packed(&mut self.last, at);
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <Option<K1> as PackedLayout>::push_packed(&self.first, at);
+        <Option<K1> as PackedLayout>::push_packed(&self.last, at);
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <Option<K1> as PackedLayout>::clear_packed(&self.first, at);
+        <Option<K1> as PackedLayout>::clear_packed(&self.last, at);
+    }
+}
+
+impl<K1, K2> SpreadLayout for Prefix<K1, K2>
+where
+    K1: PackedLayout,
+    K2: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = <K1 as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP
+        || <K2 as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<K1, K2> PackedLayout for Prefix<K1, K2>
+where
+    K1: PackedLayout,
+    K2: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <Option<K1> as PackedLayout>::pull_packed(&mut self.prev, at);
+        <Option<K1> as PackedLayout>::pull_packed(&mut self.next, at);
+        <K2 as PackedLayout>::pull_packed(&mut self.first, at);
+        <K2 as PackedLayout>::pull_packed(&mut self.last, at);
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <Option<K1> as PackedLayout>::push_packed(&self.prev, at);
+        <Option<K1> as PackedLayout>::push_packed(&self.next, at);
+        <K2 as PackedLayout>::push_packed(&self.first, at);
+        <K2 as PackedLayout>::push_packed(&self.last, at);
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <Option<K1> as PackedLayout>::clear_packed(&self.prev, at);
+        <Option<K1> as PackedLayout>::clear_packed(&self.next, at);
+        <K2 as PackedLayout>::clear_packed(&self.first, at);
+        <K2 as PackedLayout>::clear_packed(&self.last, at);
+    }
+}
+
+impl<K2, V> SpreadLayout for Entry<K2, V>
+where
+    K2: PackedLayout,
+    V: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = <K2 as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP
+        || <V as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<K2, V> PackedLayout for Entry<K2, V>
+where
+    K2: PackedLayout,
+    V: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <V as PackedLayout>::pull_packed(&mut self.value, at);
+        <Option<K2> as PackedLayout>::pull_packed(&mut self.prev, at);
+        <Option<K2> as PackedLayout>::pull_packed(&mut self.next, at);
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <V as PackedLayout>::push_packed(&self.value, at);
+        <Option<K2> as PackedLayout>::push_packed(&self.prev, at);
+        <Option<K2> as PackedLayout>::push_packed(&self.next, at);
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <V as PackedLayout>::clear_packed(&self.value, at);
+        <Option<K2> as PackedLayout>::clear_packed(&self.prev, at);
+        <Option<K2> as PackedLayout>::clear_packed(&self.next, at);
+    }
+}
+
+impl<K1, K2, V, H> SpreadLayout for StorageDoubleMap<K1, K2, V, H>
+where
+    K1: Ord + Clone + PackedLayout,
+    K2: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    const FOOTPRINT: u64 = 1
+        + <LazyHashMap<K1, Prefix<K1, K2>, H> as SpreadLayout>::FOOTPRINT
+        + <LazyHashMap<(K1, K2), Entry<K2, V>, H> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            header: SpreadLayout::pull_spread(ptr),
+            prefixes: SpreadLayout::pull_spread(ptr),
+            entries: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.header, ptr);
+        SpreadLayout::push_spread(&self.prefixes, ptr);
+        SpreadLayout::push_spread(&self.entries, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        self.clear_cells();
+        SpreadLayout::clear_spread(&self.header, ptr);
+        SpreadLayout::clear_spread(&self.prefixes, ptr);
+        SpreadLayout::clear_spread(&self.entries, ptr);
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::DoubleMap as StorageDoubleMap;
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+
+/// The storage double map used throughout the tests.
+type Allowances = StorageDoubleMap<u8, u8, i32>;
+
+/// Returns always the same `KeyPtr`.
+fn key_ptr() -> KeyPtr {
+    let root_key = Key::from([0x42; 32]);
+    KeyPtr::from(root_key)
+}
+
+/// Pushes a `DoubleMap` instance into the contract storage.
+fn push_dmap(dmap: &Allowances) {
+    SpreadLayout::push_spread(dmap, &mut key_ptr());
+}
+
+/// Pulls a `DoubleMap` instance from the contract storage.
+fn pull_dmap() -> Allowances {
+    <Allowances as SpreadLayout>::pull_spread(&mut key_ptr())
+}
+
+/// Returns the entries of the double map returned by `filled_dmap`.
+fn filled_dmap_entries() -> [(u8, u8, i32); 6] {
+    [
+        (b'A', b'x', 1),
+        (b'B', b'x', 2),
+        (b'A', b'y', 3),
+        (b'C', b'z', 4),
+        (b'A', b'z', 5),
+        (b'B', b'y', 6),
+    ]
+}
+
+/// Returns a double map with the prefixes `A`, `B` and `C`.
+fn filled_dmap() -> Allowances {
+    filled_dmap_entries().iter().copied().collect::<Allowances>()
+}
+
+/// Returns all second keys and values under the given first key.
+fn prefix_entries(dmap: &Allowances, k1: u8) -> Vec<(u8, i32)> {
+    dmap.iter_prefix(&k1).map(|(k2, v)| (*k2, *v)).collect()
+}
+
+#[test]
+fn new_works() {
+    let dmap = <Allowances>::new();
+    assert!(dmap.is_empty());
+    assert_eq!(dmap.len(), 0);
+    assert_eq!(dmap.len_prefix(&b'A'), 0);
+    assert!(dmap.iter_prefix(&b'A').next().is_none());
+    let default = <Allowances as Default>::default();
+    assert!(default.is_empty());
+}
+
+#[test]
+fn insert_and_get_works() {
+    let mut dmap = filled_dmap();
+    assert_eq!(dmap.len(), 6);
+    assert_eq!(dmap.len_prefix(&b'A'), 3);
+    assert_eq!(dmap.len_prefix(&b'B'), 2);
+    assert_eq!(dmap.len_prefix(&b'C'), 1);
+    assert_eq!(dmap.get(&b'A', &b'y'), Some(&3));
+    assert_eq!(dmap.get(&b'B', &b'y'), Some(&6));
+    assert_eq!(dmap.get(&b'C', &b'x'), None);
+    assert!(dmap.contains_key(&b'C', &b'z'));
+    assert!(!dmap.contains_key(&b'z', &b'C'));
+    // Overwriting a value does not change the order under its prefix.
+    assert_eq!(dmap.insert(b'A', b'x', 10), Some(1));
+    *dmap.get_mut(&b'A', &b'z').unwrap() += 10;
+    assert_eq!(dmap.len(), 6);
+    assert_eq!(
+        prefix_entries(&dmap, b'A'),
+        vec![(b'x', 10), (b'y', 3), (b'z', 15)]
+    );
+}
+
+#[test]
+fn iter_prefix_works() {
+    let dmap = filled_dmap();
+    let mut iter = dmap.iter_prefix(&b'A');
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.next(), Some((&b'x', &1)));
+    assert_eq!(iter.next_back(), Some((&b'z', &5)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some((&b'y', &3)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(prefix_entries(&dmap, b'B'), vec![(b'x', 2), (b'y', 6)]);
+    assert_eq!(dmap.iter_prefix(&b'D').count(), 0);
+}
+
+#[test]
+fn take_works() {
+    let mut dmap = filled_dmap();
+    // Take from the middle of a prefix:
+    assert_eq!(dmap.take(&b'A', &b'y'), Some(3));
+    assert_eq!(prefix_entries(&dmap, b'A'), vec![(b'x', 1), (b'z', 5)]);
+    // Take the front and back of a prefix:
+    assert_eq!(dmap.take(&b'A', &b'x'), Some(1));
+    assert_eq!(prefix_entries(&dmap, b'B'), vec![(b'x', 2), (b'y', 6)]);
+    assert_eq!(dmap.take(&b'B', &b'y'), Some(6));
+    assert_eq!(prefix_entries(&dmap, b'A'), vec![(b'z', 5)]);
+    assert_eq!(prefix_entries(&dmap, b'B'), vec![(b'x', 2)]);
+    // Taking a missing value does nothing:
+    assert_eq!(dmap.take(&b'A', &b'x'), None);
+    assert_eq!(dmap.take(&b'D', &b'x'), None);
+    assert_eq!(dmap.len(), 3);
+    // Taking the last value of a prefix removes the prefix:
+    assert_eq!(dmap.take(&b'A', &b'z'), Some(5));
+    assert_eq!(dmap.len_prefix(&b'A'), 0);
+    assert!(dmap.iter_prefix(&b'A').next().is_none());
+    // The prefix can be filled again afterwards:
+    assert_eq!(dmap.insert(b'A', b'w', 7), None);
+    assert_eq!(prefix_entries(&dmap, b'A'), vec![(b'w', 7)]);
+    assert_eq!(dmap.len(), 3);
+}
+
+#[test]
+fn remove_prefix_works() {
+    let mut dmap = filled_dmap();
+    assert_eq!(dmap.remove_prefix(&b'A'), 3);
+    assert_eq!(dmap.len(), 3);
+    assert_eq!(dmap.len_prefix(&b'A'), 0);
+    for k2 in &[b'x', b'y', b'z'] {
+        assert_eq!(dmap.get(&b'A', k2), None);
+    }
+    // The other prefixes are left intact:
+    assert_eq!(prefix_entries(&dmap, b'B'), vec![(b'x', 2), (b'y', 6)]);
+    assert_eq!(prefix_entries(&dmap, b'C'), vec![(b'z', 4)]);
+    // Removing a missing prefix does nothing:
+    assert_eq!(dmap.remove_prefix(&b'A'), 0);
+    assert_eq!(dmap.remove_prefix(&b'D'), 0);
+    assert_eq!(dmap.len(), 3);
+}
+
+#[test]
+fn clear_works() {
+    let mut dmap = filled_dmap();
+    dmap.clear();
+    assert!(dmap.is_empty());
+    for k1 in &[b'A', b'B', b'C'] {
+        assert_eq!(dmap.len_prefix(k1), 0);
+        assert!(dmap.iter_prefix(k1).next().is_none());
+    }
+    // The cleared double map is immediately reusable.
+    assert_eq!(dmap.insert(b'B', b'x', 1), None);
+    assert_eq!(dmap.len(), 1);
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let dmap1 = filled_dmap();
+        push_dmap(&dmap1);
+        core::mem::forget(dmap1);
+        // Load the pushed storage double map into another instance:
+        let mut dmap2 = pull_dmap();
+        assert_eq!(dmap2.len(), 6);
+        assert_eq!(
+            prefix_entries(&dmap2, b'A'),
+            vec![(b'x', 1), (b'y', 3), (b'z', 5)]
+        );
+        // Keep operating on the loaded instance across another push and pull:
+        assert_eq!(dmap2.remove_prefix(&b'B'), 2);
+        assert_eq!(dmap2.take(&b'A', &b'x'), Some(1));
+        assert_eq!(dmap2.insert(b'C', b'a', 7), None);
+        push_dmap(&dmap2);
+        core::mem::forget(dmap2);
+        let dmap3 = pull_dmap();
+        assert_eq!(dmap3.len(), 4);
+        assert_eq!(prefix_entries(&dmap3, b'A'), vec![(b'y', 3), (b'z', 5)]);
+        assert_eq!(prefix_entries(&dmap3, b'B'), vec![]);
+        assert_eq!(prefix_entries(&dmap3, b'C'), vec![(b'z', 4), (b'a', 7)]);
+        assert_eq!(dmap3.get(&b'B', &b'x'), None);
+        core::mem::forget(dmap3);
+        Ok(())
+    })
+}
+
+#[test]
+fn drop_clears_all_cells() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let dmap1 = filled_dmap();
+        push_dmap(&dmap1);
+        core::mem::forget(dmap1);
+        // Dropping a pulled instance clears the cells of all its prefixes and
+        // values from the contract storage:
+        let dmap2 = pull_dmap();
+        assert_eq!(dmap2.get(&b'C', &b'z'), Some(&4));
+        drop(dmap2);
+        let dmap3 = pull_dmap();
+        for (k1, k2, _) in filled_dmap_entries() {
+            assert_eq!(dmap3.get(&k1, &k2), None);
+        }
+        for k1 in &[b'A', b'B', b'C'] {
+            assert!(dmap3.prefixes.get(k1).is_none());
+        }
+        // Only `clear_spread` also clears the header which still refers to the
+        // removed prefixes so we must not drop this instance.
+        core::mem::forget(dmap3);
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "encountered empty storage cell")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let dmap1 = filled_dmap();
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&dmap1, &mut KeyPtr::from(root_key));
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `dmap1` and check whether
+        // loading another instance from this storage will panic since the
+        // map's header cannot be read:
+        SpreadLayout::clear_spread(&dmap1, &mut KeyPtr::from(root_key));
+        let _ = <Allowances as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        Ok(())
+    })
+    .unwrap()
+}
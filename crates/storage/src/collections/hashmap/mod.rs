@@ -314,6 +314,19 @@ where
         Some(entry.value)
     }
 
+    /// Removes all key/value pairs from the map.
+    ///
+    /// # Note
+    ///
+    /// This loads every key from the contract storage in order to find the
+    /// associated value cells. The values themselves are not read.
+    pub fn clear(&mut self) {
+        for key in self.keys.iter() {
+            self.values.put(key.clone(), None);
+        }
+        self.keys.clear();
+    }
+
     /// Returns a shared reference to the value corresponding to the key.
     ///
     /// The key may be any borrowed form of the map's key type,
@@ -169,6 +169,60 @@ fn take_works() {
     assert_eq!(hmap.len(), 0);
 }
 
+#[test]
+fn clear_works() {
+    let mut hmap = [(b'A', 1), (b'B', 2), (b'C', 3)]
+        .iter()
+        .copied()
+        .collect::<StorageHashMap<u8, i32>>();
+    assert_eq!(hmap.take(&b'B'), Some(2));
+    hmap.clear();
+    assert!(hmap.is_empty());
+    assert_eq!(hmap.get(&b'A'), None);
+    assert_eq!(hmap.get(&b'C'), None);
+    assert!(hmap.iter().next().is_none());
+    // The cleared hash map is immediately reusable.
+    assert_eq!(hmap.insert(b'A', 4), None);
+    assert_eq!(hmap.insert(b'D', 5), None);
+    assert_eq!(hmap.len(), 2);
+    assert_eq!(hmap.get(&b'A'), Some(&4));
+}
+
+#[test]
+fn clear_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let hmap1 = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        push_hmap(&hmap1);
+        // Push a hash map with a vacant key entry.
+        let mut hmap2 = pull_hmap();
+        assert_eq!(hmap2.take(&b'B'), Some(2));
+        push_hmap(&hmap2);
+        core::mem::forget(hmap2);
+        // Clear the hash map and push it again.
+        let mut hmap3 = pull_hmap();
+        assert_eq!(hmap3.keys.capacity(), 4);
+        hmap3.clear();
+        push_hmap(&hmap3);
+        core::mem::forget(hmap3);
+        // No residual entries remain in the contract storage.
+        let hmap4 = pull_hmap();
+        assert!(hmap4.is_empty());
+        assert_eq!(hmap4.keys.capacity(), 0);
+        for key in &[b'A', b'B', b'C', b'D'] {
+            assert_eq!(hmap4.get(key), None);
+        }
+        let entries_key = *hmap4.keys.entries_key().expect("pulled from storage");
+        for index in 0..4 {
+            let entry_key = entries_key + index;
+            assert_eq!(ink_env::get_contract_storage::<()>(&entry_key), Ok(None));
+        }
+        Ok(())
+    })
+}
+
 #[test]
 fn iter_next_works() {
     let hmap = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
@@ -0,0 +1,122 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    HashSet as StorageHashSet,
+    Iter,
+};
+use crate::traits::PackedLayout;
+use core::{
+    cmp::{
+        Eq,
+        Ord,
+        PartialEq,
+    },
+    iter::FromIterator,
+};
+use ink_env::hash::{
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+impl<T, H> Drop for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn drop(&mut self) {
+        self.clear_cells();
+    }
+}
+
+impl<T, H> Default for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn default() -> Self {
+        StorageHashSet::new()
+    }
+}
+
+impl<'a, T: 'a, H> IntoIterator for &'a StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, H> Extend<T> for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T, H> FromIterator<T> for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut set = StorageHashSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T, H> PartialEq for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false
+        }
+        self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<T, H> Eq for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+}
@@ -0,0 +1,122 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Links;
+use crate::{
+    collections::HashSet as StorageHashSet,
+    lazy::LazyHashMap,
+    traits::PackedLayout,
+};
+use ink_env::hash::{
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+/// An iterator over shared references to the values of a storage hash set.
+#[derive(Debug, Copy, Clone)]
+pub struct Iter<'a, T, H>
+where
+    T: PackedLayout,
+{
+    /// The lazy hash map to query the links between the values.
+    links: &'a LazyHashMap<T, Links<T>, H>,
+    /// The next value to yield from the front.
+    front: Option<&'a T>,
+    /// The next value to yield from the back.
+    back: Option<&'a T>,
+    /// The number of yet to be yielded values.
+    remaining: u32,
+}
+
+impl<'a, T, H> Iter<'a, T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Creates a new iterator for the given storage hash set.
+    pub(crate) fn new(hash_set: &'a StorageHashSet<T, H>) -> Self {
+        Self {
+            links: &hash_set.links,
+            front: hash_set.header.first.as_ref(),
+            back: hash_set.header.last.as_ref(),
+            remaining: hash_set.len(),
+        }
+    }
+
+    /// Queries the links of the given value.
+    ///
+    /// # Panics
+    ///
+    /// If the value refers to an invalid entry.
+    fn query_links(&self, value: &'a T) -> &'a Links<T> {
+        self.links
+            .get(value)
+            .expect("a linked value must always refer to an existing entry")
+    }
+}
+
+impl<'a, T, H> Iterator for Iter<'a, T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    type Item = &'a T;
+
+    fn count(self) -> usize {
+        self.remaining as usize
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let value = self.front?;
+        self.front = self.query_links(value).next.as_ref();
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, H> ExactSizeIterator for Iter<'a, T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+}
+
+impl<'a, T, H> DoubleEndedIterator for Iter<'a, T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let value = self.back?;
+        self.back = self.query_links(value).prev.as_ref();
+        self.remaining -= 1;
+        Some(value)
+    }
+}
@@ -0,0 +1,249 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage hash set that allows to efficiently query the membership of
+//! values.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::iter::Iter;
+use crate::{
+    lazy::{
+        Lazy,
+        LazyHashMap,
+    },
+    traits::PackedLayout,
+};
+use core::borrow::Borrow;
+use ink_env::hash::{
+    Blake2x256,
+    CryptoHash,
+    HashOutput,
+};
+use ink_prelude::borrow::ToOwned;
+use ink_primitives::Key;
+
+/// A hash set operating on the contract storage.
+///
+/// Stores a set of unique values.
+///
+/// # Note
+///
+/// Like the storage hash map the storage hash set uses the [`scale::Encode`]
+/// encoding of its values in order to hash them using a built-in cryptographic
+/// hash function provided by the chain runtime.
+///
+/// Unlike a `storage::HashMap<T, ()>` the hash set does not keep its values
+/// in a separate storage stash for iteration. Instead the storage cell of
+/// every value links to the cells of the values inserted right before and
+/// after it. This way the hash set requires only a single storage cell per
+/// value and iterates over its values in insertion order.
+#[derive(Debug)]
+pub struct HashSet<T, H = Blake2x256>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// The length and the first and last value of the storage hash set.
+    header: Lazy<Header<T>>,
+    /// The links between the values of the storage hash set.
+    links: LazyHashMap<T, Links<T>, H>,
+}
+
+/// Stores general commonly required information about the storage hash set.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header<T> {
+    /// The number of values stored in the hash set.
+    len: u32,
+    /// The value that has been inserted first if any.
+    first: Option<T>,
+    /// The value that has been inserted last if any.
+    last: Option<T>,
+}
+
+/// The entry of a value within the storage hash set.
+///
+/// Stores the values inserted right before and after the value.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Links<T> {
+    /// The previously inserted value if any.
+    prev: Option<T>,
+    /// The next inserted value if any.
+    next: Option<T>,
+}
+
+impl<T, H> HashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Creates a new empty storage hash set.
+    pub fn new() -> Self {
+        Self {
+            header: Lazy::new(Header {
+                len: 0,
+                first: None,
+                last: None,
+            }),
+            links: LazyHashMap::new(),
+        }
+    }
+
+    /// Returns the number of values stored in the hash set.
+    pub fn len(&self) -> u32 {
+        self.header.len
+    }
+
+    /// Returns `true` if the hash set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator yielding shared references to all values of the
+    /// hash set in insertion order.
+    ///
+    /// # Note
+    ///
+    /// - Avoid unbounded iteration over big storage hash sets.
+    /// - Prefer using methods like `Iterator::take` in order to limit the number
+    ///   of yielded elements.
+    pub fn iter(&self) -> Iter<T, H> {
+        Iter::new(self)
+    }
+
+    /// Returns the links of the given value.
+    ///
+    /// # Panics
+    ///
+    /// If the value is not contained in the hash set.
+    fn links_mut(&mut self, value: &T) -> &mut Links<T> {
+        self.links
+            .get_mut(value)
+            .expect("a linked value must always refer to an existing entry")
+    }
+
+    fn clear_cells(&self) {
+        if self.links.key().is_none() {
+            // We won't clear any storage if we are in lazy state since there
+            // probably has not been any state written to storage, yet.
+            return
+        }
+        for value in self.iter() {
+            self.links.clear_packed_at(value);
+        }
+    }
+}
+
+impl<T, H> HashSet<T, H>
+where
+    T: Ord + Eq + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Inserts the value into the set.
+    ///
+    /// Returns `true` if the set did not contain the value before.
+    ///
+    /// # Note
+    ///
+    /// If the set did contain the value it is not updated; this matters for
+    /// types that can be `==` without being identical.
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.contains(&value) {
+            return false
+        }
+        let prev = self.header.last.replace(value.clone());
+        match &prev {
+            Some(prev) => self.links_mut(prev).next = Some(value.clone()),
+            None => self.header.first = Some(value.clone()),
+        }
+        self.links.put(value, Some(Links { prev, next: None }));
+        self.header.len += 1;
+        true
+    }
+
+    /// Removes the value from the set and returns it if it was contained.
+    ///
+    /// # Note
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but `Hash` and `Eq` on the borrowed form must match those for the value type.
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = T>,
+    {
+        let links = self.links.put_get(value, None)?;
+        match &links.prev {
+            Some(prev) => self.links_mut(prev).next = links.next.clone(),
+            None => self.header.first = links.next.clone(),
+        }
+        match &links.next {
+            Some(next) => self.links_mut(next).prev = links.prev,
+            None => self.header.last = links.prev,
+        }
+        self.header.len -= 1;
+        Some(value.to_owned())
+    }
+
+    /// Removes the value from the set.
+    ///
+    /// Returns `true` if the set did contain the value.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = T>,
+    {
+        self.take(value).is_some()
+    }
+
+    /// Returns `true` if the set contains the value.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = T>,
+    {
+        // Like the storage hash map we do not resolve or prevent collisions
+        // since they are virtually impossible in a keyspace of 2^256 bit.
+        self.links.get(value).is_some()
+    }
+
+    /// Removes all values from the set.
+    ///
+    /// # Note
+    ///
+    /// This loads every value from the contract storage since the value
+    /// cells link to each other.
+    pub fn clear(&mut self) {
+        let mut next = self.header.first.take();
+        while let Some(value) = next {
+            next = self
+                .links
+                .put_get(&value, None)
+                .expect("a linked value must always refer to an existing entry")
+                .next;
+        }
+        self.header.last = None;
+        self.header.len = 0;
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of ink! storage traits.
+
+use super::{
+    HashSet as StorageHashSet,
+    Header,
+    Links,
+};
+use crate::{
+    lazy::LazyHashMap,
+    traits::{
+        forward_clear_packed,
+        forward_pull_packed,
+        forward_push_packed,
+        KeyPtr,
+        PackedLayout,
+        SpreadLayout,
+    },
+};
+use ink_env::hash::{
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::{
+        lazy::Lazy,
+        traits::{
+            LayoutCryptoHasher,
+            StorageLayout,
+        },
+    };
+    use ink_metadata::layout::{
+        CellLayout,
+        FieldLayout,
+        Layout,
+        LayoutKey,
+        StructLayout,
+    };
+    use scale_info::TypeInfo;
+
+    impl<T> StorageLayout for Header<T>
+    where
+        T: TypeInfo + 'static,
+    {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            Layout::Cell(CellLayout::new::<Header<T>>(LayoutKey::from(
+                key_ptr.advance_by(1),
+            )))
+        }
+    }
+
+    impl<T, H> StorageLayout for StorageHashSet<T, H>
+    where
+        T: TypeInfo + Ord + Clone + PackedLayout + 'static,
+        H: LayoutCryptoHasher + CryptoHash,
+        Key: From<<H as HashOutput>::Type>,
+    {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            Layout::Struct(StructLayout::new(vec![
+                FieldLayout::new(
+                    "header",
+                    <Lazy<Header<T>> as StorageLayout>::layout(key_ptr),
+                ),
+                FieldLayout::new(
+                    "links",
+                    <LazyHashMap<T, Links<T>, H> as StorageLayout>::layout(key_ptr),
+                ),
+            ]))
+        }
+    }
+};
+
+impl<T> SpreadLayout for Header<T>
+where
+    T: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = <T as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<T> PackedLayout for Header<T>
+where
+    T: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <Option<T> as PackedLayout>::pull_packed(&mut self.first, at);
+        <Option<T> as PackedLayout>::pull_packed(&mut self.last, at);
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <Option<T> as PackedLayout>::push_packed(&self.first, at);
+        <Option<T> as PackedLayout>::push_packed(&self.last, at);
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <Option<T> as PackedLayout>::clear_packed(&self.first, at);
+        <Option<T> as PackedLayout>::clear_packed(&self.last, at);
+    }
+}
+
+impl<T> SpreadLayout for Links<T>
+where
+    T: PackedLayout,
+{
+    const FOOTPRINT: u64 = 1;
+    const REQUIRES_DEEP_CLEAN_UP: bool = <T as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        forward_pull_packed::<Self>(ptr)
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        forward_push_packed::<Self>(self, ptr)
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        forward_clear_packed::<Self>(self, ptr)
+    }
+}
+
+impl<T> PackedLayout for Links<T>
+where
+    T: PackedLayout,
+{
+    fn pull_packed(&mut self, at: &Key) {
+        <Option<T> as PackedLayout>::pull_packed(&mut self.prev, at);
+        <Option<T> as PackedLayout>::pull_packed(&mut self.next, at);
+    }
+
+    fn push_packed(&self, at: &Key) {
+        <Option<T> as PackedLayout>::push_packed(&self.prev, at);
+        <Option<T> as PackedLayout>::push_packed(&self.next, at);
+    }
+
+    fn clear_packed(&self, at: &Key) {
+        <Option<T> as PackedLayout>::clear_packed(&self.prev, at);
+        <Option<T> as PackedLayout>::clear_packed(&self.next, at);
+    }
+}
+
+impl<T, H> SpreadLayout for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    const FOOTPRINT: u64 = 1 + <LazyHashMap<T, Links<T>, H> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            header: SpreadLayout::pull_spread(ptr),
+            links: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.header, ptr);
+        SpreadLayout::push_spread(&self.links, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        self.clear_cells();
+        SpreadLayout::clear_spread(&self.header, ptr);
+        SpreadLayout::clear_spread(&self.links, ptr);
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::HashSet as StorageHashSet;
+use crate::{
+    collections::HashMap as StorageHashMap,
+    traits::{
+        KeyPtr,
+        SpreadLayout,
+    },
+};
+use ink_primitives::Key;
+
+/// Returns always the same `KeyPtr`.
+fn key_ptr() -> KeyPtr {
+    let root_key = Key::from([0x42; 32]);
+    KeyPtr::from(root_key)
+}
+
+/// Pushes a `HashSet` instance into the contract storage.
+fn push_hset(hset: &StorageHashSet<u8>) {
+    SpreadLayout::push_spread(hset, &mut key_ptr());
+}
+
+/// Pulls a `HashSet` instance from the contract storage.
+fn pull_hset() -> StorageHashSet<u8> {
+    <StorageHashSet<u8> as SpreadLayout>::pull_spread(&mut key_ptr())
+}
+
+#[test]
+fn new_works() {
+    // `StorageHashSet::new`
+    let hset = <StorageHashSet<u8>>::new();
+    assert!(hset.is_empty());
+    assert_eq!(hset.len(), 0);
+    assert!(hset.iter().next().is_none());
+    // `StorageHashSet::default`
+    let default = <StorageHashSet<u8> as Default>::default();
+    assert!(default.is_empty());
+    assert_eq!(default.len(), 0);
+    assert!(default.iter().next().is_none());
+    // `StorageHashSet::new` and `StorageHashSet::default` should be equal.
+    assert_eq!(hset, default);
+}
+
+#[test]
+fn from_iterator_works() {
+    let test_values = [b'A', b'B', b'C', b'A', b'D'];
+    let hset = test_values.iter().copied().collect::<StorageHashSet<u8>>();
+    assert!(!hset.is_empty());
+    assert_eq!(hset.len(), 4);
+    assert!(hset.iter().eq([b'A', b'B', b'C', b'D'].iter()));
+}
+
+#[test]
+fn insert_and_contains_works() {
+    let mut hset = <StorageHashSet<u8>>::new();
+    assert!(!hset.contains(&b'A'));
+    assert!(hset.insert(b'A'));
+    assert!(hset.insert(b'B'));
+    // Inserting an already contained value does not change the set.
+    assert!(!hset.insert(b'A'));
+    assert_eq!(hset.len(), 2);
+    assert!(hset.contains(&b'A'));
+    assert!(hset.contains(&b'B'));
+    assert!(!hset.contains(&b'C'));
+    assert!(hset.iter().eq([b'A', b'B'].iter()));
+}
+
+#[test]
+fn take_works() {
+    let mut hset = [b'A', b'B', b'C', b'D', b'E']
+        .iter()
+        .copied()
+        .collect::<StorageHashSet<u8>>();
+    // Take a value from the middle:
+    assert_eq!(hset.take(&b'C'), Some(b'C'));
+    assert!(hset.iter().eq([b'A', b'B', b'D', b'E'].iter()));
+    // Take the first value:
+    assert_eq!(hset.take(&b'A'), Some(b'A'));
+    assert!(hset.iter().eq([b'B', b'D', b'E'].iter()));
+    // Take the last value:
+    assert!(hset.remove(&b'E'));
+    assert!(hset.iter().eq([b'B', b'D'].iter()));
+    assert!(hset.iter().rev().eq([b'D', b'B'].iter()));
+    // Taking a value that is not contained does nothing:
+    assert_eq!(hset.take(&b'C'), None);
+    assert!(!hset.remove(&b'X'));
+    assert_eq!(hset.len(), 2);
+    assert!(!hset.contains(&b'C'));
+    // A taken value can be inserted again:
+    assert!(hset.insert(b'A'));
+    assert!(hset.iter().eq([b'B', b'D', b'A'].iter()));
+    assert!(hset.remove(&b'B'));
+    assert!(hset.remove(&b'D'));
+    assert!(hset.remove(&b'A'));
+    assert!(hset.is_empty());
+    assert!(hset.iter().next().is_none());
+}
+
+#[test]
+fn clear_works() {
+    let mut hset = [b'A', b'B', b'C']
+        .iter()
+        .copied()
+        .collect::<StorageHashSet<u8>>();
+    assert!(hset.remove(&b'B'));
+    hset.clear();
+    assert!(hset.is_empty());
+    assert!(!hset.contains(&b'A'));
+    assert!(!hset.contains(&b'C'));
+    assert!(hset.iter().next().is_none());
+    // The cleared hash set is immediately reusable.
+    assert!(hset.insert(b'A'));
+    assert!(hset.insert(b'D'));
+    assert_eq!(hset.len(), 2);
+    assert!(hset.iter().eq([b'A', b'D'].iter()));
+}
+
+#[test]
+fn clear_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let hset1 = [b'A', b'B', b'C', b'D']
+            .iter()
+            .copied()
+            .collect::<StorageHashSet<u8>>();
+        push_hset(&hset1);
+        core::mem::forget(hset1);
+        // Clear the hash set and push it again.
+        let mut hset2 = pull_hset();
+        hset2.clear();
+        push_hset(&hset2);
+        core::mem::forget(hset2);
+        // No residual entries remain in the contract storage.
+        let hset3 = pull_hset();
+        assert!(hset3.is_empty());
+        for value in &[b'A', b'B', b'C', b'D'] {
+            assert!(!hset3.contains(value));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_works() {
+    let hset = [b'A', b'B', b'C', b'D']
+        .iter()
+        .copied()
+        .collect::<StorageHashSet<u8>>();
+    let mut iter = hset.iter();
+    assert_eq!(iter.size_hint(), (4, Some(4)));
+    assert_eq!(iter.next(), Some(&b'A'));
+    assert_eq!(iter.next_back(), Some(&b'D'));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some(&b'B'));
+    assert_eq!(iter.next_back(), Some(&b'C'));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!((&hset).into_iter().count(), 4);
+}
+
+#[test]
+fn eq_works() {
+    let hset1 = [b'A', b'B', b'C']
+        .iter()
+        .copied()
+        .collect::<StorageHashSet<u8>>();
+    // The insertion order does not matter for equality.
+    let mut hset2 = [b'C', b'B', b'A']
+        .iter()
+        .copied()
+        .collect::<StorageHashSet<u8>>();
+    assert_eq!(hset1, hset2);
+    assert!(hset2.remove(&b'B'));
+    assert_ne!(hset1, hset2);
+    assert!(hset2.insert(b'D'));
+    assert_ne!(hset1, hset2);
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let hset1 = [b'A', b'B', b'C', b'D']
+            .iter()
+            .copied()
+            .collect::<StorageHashSet<u8>>();
+        push_hset(&hset1);
+        // Load the pushed storage hash set into another instance and check
+        // that both instances are equal:
+        let mut hset2 = pull_hset();
+        assert_eq!(hset1, hset2);
+        assert!(hset2.iter().eq(hset1.iter()));
+        // Keep operating on the loaded instance across another push and pull:
+        assert!(hset2.remove(&b'A'));
+        assert!(hset2.remove(&b'C'));
+        assert!(hset2.insert(b'E'));
+        push_hset(&hset2);
+        let hset3 = pull_hset();
+        assert!(hset3.iter().eq([b'B', b'D', b'E'].iter()));
+        assert!(hset3.iter().rev().eq([b'E', b'D', b'B'].iter()));
+        core::mem::forget((hset1, hset2, hset3));
+        Ok(())
+    })
+}
+
+#[test]
+fn uses_half_the_cells_of_an_equivalent_hash_map() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let written_cells = || -> ink_env::Result<usize> {
+            ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                &contract_account,
+            )
+            .map(|(_, writes)| writes)
+        };
+        let hset = (0..100).collect::<StorageHashSet<u8>>();
+        let hmap = (0..100)
+            .map(|value| (value, ()))
+            .collect::<StorageHashMap<u8, ()>>();
+        let base_writes = written_cells()?;
+        SpreadLayout::push_spread(&hset, &mut KeyPtr::from(Key::from([0x01; 32])));
+        let hset_cells = written_cells()? - base_writes;
+        SpreadLayout::push_spread(&hmap, &mut KeyPtr::from(Key::from([0x02; 32])));
+        let hmap_cells = written_cells()? - base_writes - hset_cells;
+        // One cell per value plus the header.
+        assert_eq!(hset_cells, 101);
+        // One key cell and one value cell per entry plus the stash header.
+        assert_eq!(hmap_cells, 201);
+        core::mem::forget((hset, hmap));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "encountered empty storage cell")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let hset1 = [b'A', b'B', b'C', b'D']
+            .iter()
+            .copied()
+            .collect::<StorageHashSet<u8>>();
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&hset1, &mut KeyPtr::from(root_key));
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `hset1` and check whether
+        // loading another instance from this storage will panic since the
+        // set's header cannot be read:
+        SpreadLayout::clear_spread(&hset1, &mut KeyPtr::from(root_key));
+        let _ = <StorageHashSet<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(
+            root_key,
+        ));
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,86 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    Iter,
+    LinkedList as StorageLinkedList,
+};
+use crate::traits::PackedLayout;
+use core::iter::{
+    Extend,
+    FromIterator,
+};
+
+impl<T> Default for StorageLinkedList<T>
+where
+    T: PackedLayout,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: 'a> IntoIterator for &'a StorageLinkedList<T>
+where
+    T: PackedLayout,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Extend<T> for StorageLinkedList<T>
+where
+    T: PackedLayout,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for StorageLinkedList<T>
+where
+    T: PackedLayout,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut list = StorageLinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> core::cmp::PartialEq for StorageLinkedList<T>
+where
+    T: PartialEq + PackedLayout,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false
+        }
+        self.iter().zip(other.iter()).all(|(lhs, rhs)| lhs == rhs)
+    }
+}
+
+impl<T> core::cmp::Eq for StorageLinkedList<T> where T: Eq + PackedLayout {}
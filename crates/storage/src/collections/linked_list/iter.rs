@@ -0,0 +1,93 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    Index,
+    LinkedList as StorageLinkedList,
+};
+use crate::traits::PackedLayout;
+
+/// An iterator over shared references to the elements of a storage linked list.
+#[derive(Debug, Clone, Copy)]
+pub struct Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    /// The storage linked list to iterate over.
+    list: &'a StorageLinkedList<T>,
+    /// The next node to yield from the front.
+    front: Option<Index>,
+    /// The next node to yield from the back.
+    back: Option<Index>,
+    /// The number of yet to be yielded elements.
+    remaining: u32,
+}
+
+impl<'a, T> Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new iterator for the given storage linked list.
+    pub(crate) fn new(list: &'a StorageLinkedList<T>) -> Self {
+        Self {
+            list,
+            front: list.header.front,
+            back: list.header.back,
+            remaining: list.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let node = self.list.node(self.front?);
+        self.front = node.next;
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining as usize
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: PackedLayout {}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None
+        }
+        let node = self.list.node(self.back?);
+        self.back = node.prev;
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+}
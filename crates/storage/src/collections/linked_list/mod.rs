@@ -0,0 +1,282 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage doubly linked list that allows to remove elements from anywhere
+//! within the list in constant time given a handle to them.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::iter::Iter;
+use crate::{
+    collections::Stash,
+    lazy::Lazy,
+    traits::PackedLayout,
+};
+
+/// The index of a node within the underlying storage stash.
+type Index = u32;
+
+/// A doubly linked list operating on the contract storage.
+///
+/// # Note
+///
+/// Every element is stored together with the handles to its neighbours in a
+/// single storage cell. The nodes are kept in a storage stash so that the
+/// cells of removed nodes are reused by subsequently inserted nodes.
+///
+/// Inserting an element returns a [`NodeHandle`] that can be used to access
+/// or remove the element in constant time later on.
+#[derive(Debug)]
+pub struct LinkedList<T>
+where
+    T: PackedLayout,
+{
+    /// The handles to the front and back nodes.
+    header: Lazy<Header>,
+    /// The nodes of the linked list.
+    nodes: Stash<Node<T>>,
+}
+
+/// A handle to a node of a storage linked list.
+///
+/// # Note
+///
+/// A handle stays valid until the node it refers to is removed.
+/// Since the storage of removed nodes is reused, a handle to a removed node
+/// might eventually refer to another node that is inserted later on.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, scale::Encode, scale::Decode,
+)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NodeHandle(Index);
+
+/// Stores general commonly required information about the storage linked list.
+#[derive(Debug, Default, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header {
+    /// The front node of the list if any.
+    front: Option<Index>,
+    /// The back node of the list if any.
+    back: Option<Index>,
+}
+
+/// A node of the linked list.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Node<T> {
+    /// The element stored in the node.
+    value: T,
+    /// The previous node if any.
+    prev: Option<Index>,
+    /// The next node if any.
+    next: Option<Index>,
+}
+
+impl<T> LinkedList<T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new empty storage linked list.
+    pub fn new() -> Self {
+        Self {
+            header: Lazy::new(Header::default()),
+            nodes: Stash::new(),
+        }
+    }
+
+    /// Returns the number of elements in the linked list.
+    pub fn len(&self) -> u32 {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the linked list contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator yielding shared references to all elements of the
+    /// linked list from front to back.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage linked lists.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter(&self) -> Iter<T> {
+        Iter::new(self)
+    }
+
+    /// Returns the node at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If the index does not refer to a node of the linked list.
+    fn node(&self, at: Index) -> &Node<T> {
+        self.nodes
+            .get(at)
+            .expect("encountered invalid linked list node index")
+    }
+
+    /// Returns the node at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If the index does not refer to a node of the linked list.
+    fn node_mut(&mut self, at: Index) -> &mut Node<T> {
+        self.nodes
+            .get_mut(at)
+            .expect("encountered invalid linked list node index")
+    }
+
+    /// Returns a handle to the front node if any.
+    pub fn front_handle(&self) -> Option<NodeHandle> {
+        self.header.front.map(NodeHandle)
+    }
+
+    /// Returns a handle to the back node if any.
+    pub fn back_handle(&self) -> Option<NodeHandle> {
+        self.header.back.map(NodeHandle)
+    }
+
+    /// Returns a handle to the node after the given node if any.
+    pub fn next_handle(&self, handle: NodeHandle) -> Option<NodeHandle> {
+        self.nodes.get(handle.0)?.next.map(NodeHandle)
+    }
+
+    /// Returns a handle to the node before the given node if any.
+    pub fn prev_handle(&self, handle: NodeHandle) -> Option<NodeHandle> {
+        self.nodes.get(handle.0)?.prev.map(NodeHandle)
+    }
+
+    /// Returns a shared reference to the element of the given node.
+    ///
+    /// Returns `None` if the handle does not refer to a node of the list.
+    pub fn get(&self, handle: NodeHandle) -> Option<&T> {
+        self.nodes.get(handle.0).map(|node| &node.value)
+    }
+
+    /// Returns an exclusive reference to the element of the given node.
+    ///
+    /// Returns `None` if the handle does not refer to a node of the list.
+    pub fn get_mut(&mut self, handle: NodeHandle) -> Option<&mut T> {
+        self.nodes.get_mut(handle.0).map(|node| &mut node.value)
+    }
+
+    /// Returns a shared reference to the front element if any.
+    pub fn front(&self) -> Option<&T> {
+        Some(&self.node(self.header.front?).value)
+    }
+
+    /// Returns an exclusive reference to the front element if any.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        let front = self.header.front?;
+        Some(&mut self.node_mut(front).value)
+    }
+
+    /// Returns a shared reference to the back element if any.
+    pub fn back(&self) -> Option<&T> {
+        Some(&self.node(self.header.back?).value)
+    }
+
+    /// Returns an exclusive reference to the back element if any.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        let back = self.header.back?;
+        Some(&mut self.node_mut(back).value)
+    }
+
+    /// Appends an element to the back of the linked list.
+    ///
+    /// Returns a handle to the node of the new element.
+    pub fn push_back(&mut self, value: T) -> NodeHandle {
+        let prev = self.header.back;
+        let index = self.nodes.put(Node {
+            value,
+            prev,
+            next: None,
+        });
+        match prev {
+            Some(prev) => self.node_mut(prev).next = Some(index),
+            None => self.header.front = Some(index),
+        }
+        self.header.back = Some(index);
+        NodeHandle(index)
+    }
+
+    /// Prepends an element to the front of the linked list.
+    ///
+    /// Returns a handle to the node of the new element.
+    pub fn push_front(&mut self, value: T) -> NodeHandle {
+        let next = self.header.front;
+        let index = self.nodes.put(Node {
+            value,
+            prev: None,
+            next,
+        });
+        match next {
+            Some(next) => self.node_mut(next).prev = Some(index),
+            None => self.header.back = Some(index),
+        }
+        self.header.front = Some(index);
+        NodeHandle(index)
+    }
+
+    /// Removes the front element from the linked list and returns it.
+    ///
+    /// Returns `None` if the linked list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let front = self.front_handle()?;
+        self.remove(front)
+    }
+
+    /// Removes the back element from the linked list and returns it.
+    ///
+    /// Returns `None` if the linked list is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let back = self.back_handle()?;
+        self.remove(back)
+    }
+
+    /// Removes the element of the given node from the linked list and returns it.
+    ///
+    /// Returns `None` and does not mutate the list if the handle does not
+    /// refer to a node of the list.
+    ///
+    /// # Note
+    ///
+    /// This operation only loads the neighbours of the removed node.
+    pub fn remove(&mut self, handle: NodeHandle) -> Option<T> {
+        let node = self.nodes.take(handle.0)?;
+        match node.prev {
+            Some(prev) => self.node_mut(prev).next = node.next,
+            None => self.header.front = node.next,
+        }
+        match node.next {
+            Some(next) => self.node_mut(next).prev = node.prev,
+            None => self.header.back = node.prev,
+        }
+        Some(node.value)
+    }
+
+    /// Removes all elements from the linked list.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.header.front = None;
+        self.header.back = None;
+    }
+}
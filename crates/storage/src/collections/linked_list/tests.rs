@@ -0,0 +1,222 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    LinkedList as StorageLinkedList,
+    NodeHandle,
+};
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+use std::collections::VecDeque as StdVecDeque;
+
+/// Creates a storage linked list from the given slice.
+fn list_from_slice(slice: &[u8]) -> StorageLinkedList<u8> {
+    slice.iter().copied().collect::<StorageLinkedList<u8>>()
+}
+
+/// Asserts that the storage linked list holds exactly the given elements in order.
+fn assert_elems(list: &StorageLinkedList<u8>, expected: &[u8]) {
+    assert_eq!(list.len() as usize, expected.len());
+    assert_eq!(list.is_empty(), expected.is_empty());
+    assert_eq!(list.front(), expected.first());
+    assert_eq!(list.back(), expected.last());
+    assert!(list.iter().eq(expected.iter()));
+    assert!(list.iter().rev().eq(expected.iter().rev()));
+}
+
+#[test]
+fn new_default_works() {
+    let list = <StorageLinkedList<u8>>::new();
+    assert!(list.is_empty());
+    assert_eq!(list.len(), 0);
+    assert_eq!(list.front(), None);
+    assert_eq!(list.back(), None);
+    assert_eq!(list.front_handle(), None);
+    assert_eq!(list.back_handle(), None);
+    assert!(list.iter().next().is_none());
+    let default = <StorageLinkedList<u8> as Default>::default();
+    assert!(default.is_empty());
+    assert_eq!(list, default);
+}
+
+#[test]
+fn push_and_pop_works() {
+    let mut list = <StorageLinkedList<u8>>::new();
+    list.push_back(b'b');
+    list.push_front(b'a');
+    list.push_back(b'c');
+    assert_elems(&list, &[b'a', b'b', b'c']);
+    assert_eq!(list.pop_front(), Some(b'a'));
+    assert_eq!(list.pop_back(), Some(b'c'));
+    assert_eq!(list.pop_back(), Some(b'b'));
+    assert_eq!(list.pop_back(), None);
+    assert_eq!(list.pop_front(), None);
+    assert_elems(&list, &[]);
+}
+
+#[test]
+fn handles_work() {
+    let mut list = <StorageLinkedList<u8>>::new();
+    let b = list.push_back(b'b');
+    let a = list.push_front(b'a');
+    let c = list.push_back(b'c');
+    assert_eq!(list.front_handle(), Some(a));
+    assert_eq!(list.back_handle(), Some(c));
+    assert_eq!(list.next_handle(a), Some(b));
+    assert_eq!(list.next_handle(c), None);
+    assert_eq!(list.prev_handle(c), Some(b));
+    assert_eq!(list.prev_handle(a), None);
+    assert_eq!(list.get(b), Some(&b'b'));
+    *list.get_mut(b).unwrap() = b'x';
+    assert_elems(&list, &[b'a', b'x', b'c']);
+}
+
+#[test]
+fn remove_works() {
+    let mut list = list_from_slice(&[b'a', b'b', b'c', b'd', b'e']);
+    let handles = core::iter::successors(list.front_handle(), |&handle| {
+        list.next_handle(handle)
+    })
+    .collect::<Vec<_>>();
+    assert_eq!(handles.len(), 5);
+    // Remove from the middle:
+    assert_eq!(list.remove(handles[2]), Some(b'c'));
+    assert_elems(&list, &[b'a', b'b', b'd', b'e']);
+    // Remove the front:
+    assert_eq!(list.remove(handles[0]), Some(b'a'));
+    assert_elems(&list, &[b'b', b'd', b'e']);
+    // Remove the back:
+    assert_eq!(list.remove(handles[4]), Some(b'e'));
+    assert_elems(&list, &[b'b', b'd']);
+    // Removing the same node again does nothing:
+    assert_eq!(list.remove(handles[2]), None);
+    assert_eq!(list.get(handles[2]), None);
+    assert_elems(&list, &[b'b', b'd']);
+    // Remove the remaining nodes:
+    assert_eq!(list.remove(handles[3]), Some(b'd'));
+    assert_eq!(list.remove(handles[1]), Some(b'b'));
+    assert_elems(&list, &[]);
+}
+
+#[test]
+fn removed_nodes_are_recycled() {
+    let mut list = list_from_slice(&[b'a', b'b', b'c']);
+    let b = list.next_handle(list.front_handle().unwrap()).unwrap();
+    assert_eq!(list.remove(b), Some(b'b'));
+    // The storage of the removed node is reused by the next inserted node.
+    assert_eq!(list.push_front(b'x'), b);
+    assert_elems(&list, &[b'x', b'a', b'c']);
+    assert_eq!(list.nodes.capacity(), 3);
+}
+
+#[test]
+fn clear_works() {
+    let mut list = list_from_slice(&[b'a', b'b', b'c']);
+    list.clear();
+    assert_elems(&list, &[]);
+    // The list is still usable after clearing it.
+    list.push_back(b'a');
+    assert_elems(&list, &[b'a']);
+}
+
+#[test]
+fn iteration_after_heavy_churn_works() {
+    let mut list = <StorageLinkedList<u8>>::new();
+    let mut expected = StdVecDeque::<(NodeHandle, u8)>::new();
+    let mut seed = 0x2545_f491_u32;
+    let mut peak_len = 0;
+    for round in 0..2000 {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        let value = (round % 256) as u8;
+        match seed % 5 {
+            0 => expected.push_back((list.push_back(value), value)),
+            1 => expected.push_front((list.push_front(value), value)),
+            2 => {
+                assert_eq!(list.pop_front(), expected.pop_front().map(|(_, v)| v))
+            }
+            _ if expected.is_empty() => assert_eq!(list.pop_back(), None),
+            _ => {
+                let at = (seed >> 8) as usize % expected.len();
+                let (handle, value) = expected.remove(at).unwrap();
+                assert_eq!(list.remove(handle), Some(value));
+            }
+        }
+        peak_len = core::cmp::max(peak_len, list.len());
+        if round % 50 == 0 {
+            let values = expected.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+            assert_elems(&list, &values);
+        }
+    }
+    let values = expected.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+    assert_elems(&list, &values);
+    // Removed nodes are recycled so the number of used storage cells never
+    // exceeds the peak length of the list.
+    assert_eq!(list.nodes.capacity(), peak_len);
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut list1 = list_from_slice(&[b'b', b'c', b'd']);
+        let a = list1.push_front(b'a');
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&list1, &mut KeyPtr::from(root_key));
+        // Load the pushed storage linked list into another instance and check
+        // that both instances are equal:
+        let mut list2 = <StorageLinkedList<u8> as SpreadLayout>::pull_spread(
+            &mut KeyPtr::from(root_key),
+        );
+        assert_eq!(list1, list2);
+        // Handles stay valid across pushing and pulling:
+        assert_eq!(list2.get(a), Some(&b'a'));
+        let c = list2.prev_handle(list2.back_handle().unwrap()).unwrap();
+        assert_eq!(list2.remove(c), Some(b'c'));
+        list2.push_back(b'e');
+        SpreadLayout::push_spread(&list2, &mut KeyPtr::from(root_key));
+        let list3 = <StorageLinkedList<u8> as SpreadLayout>::pull_spread(
+            &mut KeyPtr::from(root_key),
+        );
+        assert_elems(&list3, &[b'a', b'b', b'd', b'e']);
+        // Avoid clearing the storage when dropping the instances:
+        core::mem::forget((list1, list2, list3));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "storage entry was empty")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let list1 = list_from_slice(&[b'a', b'b', b'c', b'd']);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&list1, &mut KeyPtr::from(root_key));
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `list1` and check whether
+        // loading another instance from this storage will panic since the
+        // list's header cannot be read:
+        SpreadLayout::clear_spread(&list1, &mut KeyPtr::from(root_key));
+        let _ = <StorageLinkedList<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(
+            root_key,
+        ));
+        Ok(())
+    })
+    .unwrap()
+}
@@ -21,9 +21,16 @@
 pub mod binary_heap;
 pub mod bitstash;
 pub mod bitvec;
+pub mod bounded_vec;
+pub mod btreemap;
+pub mod deque;
+pub mod double_map;
 pub mod hashmap;
+pub mod hashset;
+pub mod linked_list;
 pub mod smallvec;
 pub mod stash;
+pub mod string;
 pub mod vec;
 
 #[doc(inline)]
@@ -31,9 +38,16 @@ pub use self::{
     binary_heap::BinaryHeap,
     bitstash::BitStash,
     bitvec::Bitvec,
+    bounded_vec::BoundedVec,
+    btreemap::BTreeMap,
+    deque::Deque,
+    double_map::DoubleMap,
     hashmap::HashMap,
+    hashset::HashSet,
+    linked_list::LinkedList,
     smallvec::SmallVec,
     stash::Stash,
+    string::String,
     vec::Vec,
 };
 
@@ -522,6 +522,22 @@ where
         Some(())
     }
 
+    /// Removes all elements from the stash.
+    ///
+    /// # Note
+    ///
+    /// This also removes all vacant entries so that the stash does not hold
+    /// any storage cells afterwards. None of the elements are read from the
+    /// contract storage.
+    pub fn clear(&mut self) {
+        for index in 0..self.len_entries() {
+            self.entries.put(index, None);
+        }
+        self.header.last_vacant = 0;
+        self.header.len = 0;
+        self.header.len_entries = 0;
+    }
+
     /// Defragments the underlying storage to minimize footprint.
     ///
     /// Returns the number of storage cells freed this way.
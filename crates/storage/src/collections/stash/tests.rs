@@ -135,6 +135,25 @@ fn remove_out_of_bounds_works() {
     assert_eq!(unsafe { stash.remove_occupied(3) }, None);
 }
 
+#[test]
+fn clear_works() {
+    let mut stash = [b'A', b'B', b'C']
+        .iter()
+        .copied()
+        .collect::<StorageStash<_>>();
+    assert_eq!(stash.take(1), Some(b'B'));
+    stash.clear();
+    assert!(stash.is_empty());
+    assert_eq!(stash.capacity(), 0);
+    assert_eq!(stash.get(0), None);
+    assert!(stash.iter().next().is_none());
+    // The cleared stash is immediately reusable.
+    assert_eq!(stash.put(b'D'), 0);
+    assert_eq!(stash.put(b'E'), 1);
+    assert_eq!(stash.len(), 2);
+    assert_eq!(stash.capacity(), 2);
+}
+
 #[test]
 fn remove_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
     ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -0,0 +1,88 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::String as StorageString;
+use core::iter::FromIterator;
+
+impl Default for StorageString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> From<&'a str> for StorageString {
+    fn from(string: &'a str) -> Self {
+        let mut storage_string = Self::new();
+        storage_string.push_str(string);
+        storage_string
+    }
+}
+
+impl PartialEq for StorageString {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false
+        }
+        self.chunks.eq(&other.chunks)
+    }
+}
+
+impl Eq for StorageString {}
+
+impl PartialEq<str> for StorageString {
+    fn eq(&self, other: &str) -> bool {
+        if self.len() as usize != other.len() {
+            return false
+        }
+        self.as_bytes_iter().eq(other.bytes())
+    }
+}
+
+impl<'a> PartialEq<&'a str> for StorageString {
+    fn eq(&self, other: &&'a str) -> bool {
+        <Self as PartialEq<str>>::eq(self, other)
+    }
+}
+
+impl<'a> Extend<&'a str> for StorageString {
+    fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
+        for string in iter {
+            self.push_str(string)
+        }
+    }
+}
+
+impl Extend<char> for StorageString {
+    fn extend<T: IntoIterator<Item = char>>(&mut self, iter: T) {
+        for ch in iter {
+            self.push(ch)
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for StorageString {
+    fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
+        let mut string = Self::new();
+        string.extend(iter);
+        string
+    }
+}
+
+impl FromIterator<char> for StorageString {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        let mut string = Self::new();
+        string.extend(iter);
+        string
+    }
+}
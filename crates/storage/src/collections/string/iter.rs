@@ -0,0 +1,66 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    Chunk,
+    String as StorageString,
+};
+use crate::collections::vec::Iter as StorageVecIter;
+
+/// An iterator over the UTF-8 encoded bytes of a storage string.
+#[derive(Debug, Clone)]
+pub struct Bytes<'a> {
+    /// The chunks that have not yet been visited.
+    chunks: StorageVecIter<'a, Chunk>,
+    /// The bytes of the current chunk that have not yet been yielded.
+    current: core::slice::Iter<'a, u8>,
+    /// The number of yet to be yielded bytes.
+    remaining: u32,
+}
+
+impl<'a> Bytes<'a> {
+    /// Creates a new iterator over the bytes of the given storage string.
+    pub(crate) fn new(string: &'a StorageString) -> Self {
+        Self {
+            chunks: string.chunks.iter(),
+            current: [].iter(),
+            remaining: string.len(),
+        }
+    }
+}
+
+impl<'a> Iterator for Bytes<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(byte) = self.current.next() {
+                self.remaining -= 1;
+                return Some(*byte)
+            }
+            self.current = self.chunks.next()?.iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining as usize
+    }
+}
+
+impl<'a> ExactSizeIterator for Bytes<'a> {}
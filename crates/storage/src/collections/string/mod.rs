@@ -0,0 +1,142 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage string that distributes its UTF-8 encoded bytes over multiple
+//! storage cells.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::iter::Bytes;
+use crate::{
+    Lazy,
+    Vec as StorageVec,
+};
+use core::convert::TryFrom;
+use ink_prelude::vec::Vec;
+
+/// The maximum number of bytes stored in a single storage cell.
+const CHUNK_LEN: u32 = 32;
+
+/// A chunk of up to `CHUNK_LEN` bytes of the storage string.
+type Chunk = Vec<u8>;
+
+/// A UTF-8 encoded growable string operating on the contract storage.
+///
+/// # Note
+///
+/// Organizes its bytes in chunks of 32 bytes each stored in its own cell.
+/// Appending to the string therefore only loads and writes the cells of the
+/// last chunk and of the newly created chunks instead of the whole string.
+/// Chunk boundaries do not respect character boundaries.
+#[derive(Debug)]
+pub struct String {
+    /// The length of the string in bytes.
+    len: Lazy<u32>,
+    /// The bytes of the string.
+    ///
+    /// All chunks except the last one are completely filled.
+    chunks: StorageVec<Chunk>,
+}
+
+impl String {
+    /// Creates a new empty storage string.
+    pub fn new() -> Self {
+        Self {
+            len: Lazy::from(0),
+            chunks: StorageVec::new(),
+        }
+    }
+
+    /// Creates a new storage string from the given UTF-8 encoded bytes.
+    ///
+    /// # Errors
+    ///
+    /// If the given bytes are not valid UTF-8.
+    pub fn from_utf8(bytes: &[u8]) -> Result<Self, core::str::Utf8Error> {
+        let string = core::str::from_utf8(bytes)?;
+        Ok(Self::from(string))
+    }
+
+    /// Returns the length of the string in bytes.
+    pub fn len(&self) -> u32 {
+        *self.len
+    }
+
+    /// Returns `true` if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the UTF-8 encoded bytes of the string.
+    pub fn as_bytes_iter(&self) -> Bytes {
+        Bytes::new(self)
+    }
+
+    /// Loads the whole string from the contract storage into memory.
+    pub fn to_memory_string(&self) -> ink_prelude::string::String {
+        let bytes = self.as_bytes_iter().collect::<Vec<_>>();
+        ink_prelude::string::String::from_utf8(bytes)
+            .expect("encountered invalid UTF-8 in storage string")
+    }
+
+    /// Appends the given string slice to the end of this string.
+    ///
+    /// # Panics
+    ///
+    /// If the string would store more than `2^32 - 1` bytes.
+    pub fn push_str(&mut self, string: &str) {
+        let mut bytes = string.as_bytes();
+        let new_len = u32::try_from(bytes.len())
+            .ok()
+            .and_then(|len| self.len().checked_add(len))
+            .expect("cannot store more than `2^32 - 1` bytes in a storage string");
+        if bytes.is_empty() {
+            return
+        }
+        let filled = self.len() % CHUNK_LEN;
+        if filled != 0 {
+            let tail = self
+                .chunks
+                .last_mut()
+                .expect("encountered missing last chunk of storage string");
+            let take = core::cmp::min((CHUNK_LEN - filled) as usize, bytes.len());
+            tail.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+        }
+        for chunk in bytes.chunks(CHUNK_LEN as usize) {
+            self.chunks.push(chunk.to_vec());
+        }
+        *self.len = new_len;
+    }
+
+    /// Appends the given character to the end of this string.
+    pub fn push(&mut self, ch: char) {
+        self.push_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Removes all bytes from the string.
+    ///
+    /// # Note
+    ///
+    /// This does not read any of the chunks from the contract storage.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        *self.len = 0;
+    }
+}
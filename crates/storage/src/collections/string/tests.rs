@@ -0,0 +1,253 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    String as StorageString,
+    CHUNK_LEN,
+};
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+
+/// A string of 100 bytes spanning 4 chunks with characters of different widths.
+const TEXT_100: &str = "The quick brown fox jumps over the lazy dog. \
+                        Größenwahn ändert nichts. ∑ ≠ ∞ — ok, 😀!!";
+
+/// Returns the storage key of the chunk at the given index for a storage
+/// string stored at the given root key.
+///
+/// The chunks are stored after the string's length and the chunk vector's
+/// length fields.
+fn chunk_key(root_key: Key, index: u64) -> Key {
+    root_key + 2 + index
+}
+
+#[test]
+fn new_default_works() {
+    let string = StorageString::new();
+    assert!(string.is_empty());
+    assert_eq!(string.len(), 0);
+    assert_eq!(string.as_bytes_iter().next(), None);
+    assert_eq!(string.to_memory_string(), "");
+    assert_eq!(string, "");
+    let default = <StorageString as Default>::default();
+    assert_eq!(string, default);
+}
+
+#[test]
+fn multi_chunk_string_works() {
+    assert_eq!(TEXT_100.len(), 100);
+    let string = StorageString::from(TEXT_100);
+    assert_eq!(string.len(), 100);
+    assert_eq!(string.chunks.len(), 4);
+    assert!(string.chunks.iter().take(3).all(|chunk| chunk.len() == 32));
+    assert_eq!(string.chunks.last().map(Vec::len), Some(4));
+    assert!(string.as_bytes_iter().eq(TEXT_100.bytes()));
+    assert_eq!(string.as_bytes_iter().len(), 100);
+    assert_eq!(string.to_memory_string(), TEXT_100);
+    assert_eq!(string, TEXT_100);
+    assert_ne!(string, &TEXT_100[..99]);
+    assert_ne!(string, "");
+}
+
+#[test]
+fn from_utf8_works() {
+    let string = StorageString::from_utf8(TEXT_100.as_bytes()).unwrap();
+    assert_eq!(string, TEXT_100);
+    assert_eq!(StorageString::from_utf8(&[]).unwrap(), "");
+}
+
+#[test]
+fn from_invalid_utf8_fails() {
+    // A lone continuation byte:
+    assert!(StorageString::from_utf8(&[b'a', 0x80, b'b']).is_err());
+    // A truncated 4 byte sequence:
+    let emoji = "😀".as_bytes();
+    assert!(StorageString::from_utf8(&emoji[..3]).is_err());
+    // An encoded surrogate:
+    assert!(StorageString::from_utf8(&[0xED, 0xA0, 0x80]).is_err());
+}
+
+#[test]
+fn push_str_across_chunk_boundary_works() {
+    let mut string = StorageString::from("a".repeat(30).as_str());
+    assert_eq!(string.chunks.len(), 1);
+    // The first two bytes of the emoji fill the first chunk while the
+    // remaining bytes end up in the second chunk.
+    string.push_str("😀");
+    assert_eq!(string.len(), 34);
+    assert_eq!(string.chunks.len(), 2);
+    assert_eq!(string.chunks.get(1).map(Vec::len), Some(2));
+    // Appending exactly up to the chunk boundary does not create another chunk.
+    string.push_str(&"b".repeat(30));
+    assert_eq!(string.len(), 64);
+    assert_eq!(string.chunks.len(), 2);
+    string.push('c');
+    string.push_str("");
+    assert_eq!(string.chunks.len(), 3);
+    let expected = format!("{}😀{}c", "a".repeat(30), "b".repeat(30));
+    assert_eq!(string, expected.as_str());
+    assert_eq!(string.to_memory_string(), expected);
+}
+
+#[test]
+fn extend_and_collect_works() {
+    let words = ["lorem ", "ipsum ", "dolor ", "sit ", "amet"];
+    let string = words.iter().copied().collect::<StorageString>();
+    assert_eq!(string, "lorem ipsum dolor sit amet");
+    let mut string = "größe".chars().collect::<StorageString>();
+    string.extend("nwahn".chars());
+    assert_eq!(string, "größenwahn");
+    assert_eq!(string.len(), 12);
+}
+
+#[test]
+fn eq_works() {
+    let mut string1 = StorageString::from("hello");
+    let string2 = StorageString::from("hello world");
+    assert_ne!(string1, string2);
+    string1.push_str(" world");
+    assert_eq!(string1, string2);
+    assert_eq!(string1, "hello world");
+    assert_ne!(string1, "hello_world");
+}
+
+#[test]
+fn clear_works() {
+    let mut string = StorageString::from(TEXT_100);
+    string.clear();
+    assert!(string.is_empty());
+    assert_eq!(string.chunks.len(), 0);
+    assert_eq!(string, "");
+    // The string is still usable after clearing it.
+    string.push_str("abc");
+    assert_eq!(string, "abc");
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let string1 = StorageString::from(TEXT_100);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&string1, &mut KeyPtr::from(root_key));
+        // Load the pushed storage string into another instance and check that
+        // both instances are equal:
+        let mut string2 =
+            <StorageString as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(string1, string2);
+        assert_eq!(string2.to_memory_string(), TEXT_100);
+        // Appending to a loaded instance persists as well:
+        string2.push_str(&"x".repeat(CHUNK_LEN as usize));
+        SpreadLayout::push_spread(&string2, &mut KeyPtr::from(root_key));
+        let string3 =
+            <StorageString as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(string3.len(), 132);
+        assert_eq!(
+            string3.to_memory_string(),
+            format!("{}{}", TEXT_100, "x".repeat(32))
+        );
+        // Avoid clearing the storage when dropping the instances:
+        core::mem::forget((string1, string2, string3));
+        Ok(())
+    })
+}
+
+#[test]
+fn push_str_only_writes_tail_chunks() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let string1 = StorageString::from(TEXT_100);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&string1, &mut KeyPtr::from(root_key));
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let mut string2 =
+            <StorageString as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let (_, base_writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        // Appending within the last chunk writes the string's length and the
+        // last chunk.
+        string2.push_str("tail");
+        SpreadLayout::push_spread(&string2, &mut KeyPtr::from(root_key));
+        let (_, writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        assert_eq!(writes - base_writes, 2);
+        // Appending across the chunk boundary additionally writes the new
+        // chunk and the length of the chunk vector.
+        let mut string3 =
+            <StorageString as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        string3.push_str(&"y".repeat(30));
+        SpreadLayout::push_spread(&string3, &mut KeyPtr::from(root_key));
+        let (_, writes_after) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        assert_eq!(writes_after - writes, 4);
+        core::mem::forget((string1, string2, string3));
+        Ok(())
+    })
+}
+
+#[test]
+fn cleared_chunks_are_removed_from_storage() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let string1 = StorageString::from(TEXT_100);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&string1, &mut KeyPtr::from(root_key));
+        for index in 0..4 {
+            assert!(ink_env::get_contract_storage::<Vec<u8>>(&chunk_key(root_key, index))
+                .unwrap()
+                .is_some());
+        }
+        let mut string2 =
+            <StorageString as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        string2.clear();
+        SpreadLayout::push_spread(&string2, &mut KeyPtr::from(root_key));
+        for index in 0..4 {
+            assert_eq!(
+                ink_env::get_contract_storage::<Vec<u8>>(&chunk_key(root_key, index)),
+                Ok(None)
+            );
+        }
+        let string3 =
+            <StorageString as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(string3.is_empty());
+        core::mem::forget((string1, string2, string3));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "encountered empty storage cell")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let string1 = StorageString::from(TEXT_100);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&string1, &mut KeyPtr::from(root_key));
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `string1` and check whether
+        // loading another instance from this storage will panic since the
+        // string's length field cannot be read:
+        SpreadLayout::clear_spread(&string1, &mut KeyPtr::from(root_key));
+        let _ =
+            <StorageString as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        Ok(())
+    })
+    .unwrap()
+}
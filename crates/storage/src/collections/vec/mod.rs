@@ -35,6 +35,7 @@ use crate::{
     },
     traits::PackedLayout,
 };
+use core::cmp::Ordering;
 
 /// A contiguous growable array type, written `Vec<T>` but pronounced 'vector'.
 ///
@@ -180,6 +181,50 @@ where
         self.within_bounds(index)
             .and_then(|index| self.elems.get(index))
     }
+
+    /// Binary searches this sorted vector for the given element.
+    ///
+    /// Returns `Ok` with the index of a matching element if found.
+    /// If there are multiple matches any one of them may be returned.
+    /// Returns `Err` with the index where a matching element could be inserted
+    /// while maintaining sorted order otherwise.
+    ///
+    /// # Note
+    ///
+    /// This loads `O(log n)` elements from the contract storage.
+    pub fn binary_search(&self, x: &T) -> Result<u32, u32>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|elem| elem.cmp(x))
+    }
+
+    /// Binary searches this sorted vector with a comparator function.
+    ///
+    /// The comparator function must return the ordering of the given element
+    /// relative to the searched target, consistent with the sort order of the
+    /// vector.
+    ///
+    /// See [`Vec::binary_search`] for the semantics of the returned value.
+    pub fn binary_search_by<'a, F>(&'a self, mut f: F) -> Result<u32, u32>
+    where
+        F: FnMut(&'a T) -> Ordering,
+    {
+        let mut left = 0;
+        let mut right = self.len();
+        while left < right {
+            let mid = left + (right - left) / 2;
+            let elem = self
+                .get(mid)
+                .expect("index is within bounds of the vector");
+            match f(elem) {
+                Ordering::Less => left = mid + 1,
+                Ordering::Greater => right = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(left)
+    }
 }
 
 impl<T> Vec<T>
@@ -196,6 +241,32 @@ where
         *self.len += 1;
         self.elems.put(last_index, Some(value));
     }
+
+    /// Inserts an element at position `index` shifting all elements after it
+    /// to the right.
+    ///
+    /// # Note
+    ///
+    /// This operation has to move all elements after `index` and therefore
+    /// performs `O(n)` contract storage reads and writes.
+    /// Prefer [`Vec::push`] if the order of elements is irrelevant.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is greater than the length of the vector.
+    pub fn insert(&mut self, index: u32, value: T) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+        self.push(value);
+        for n in (index..len).rev() {
+            self.elems.swap(n, n + 1);
+        }
+    }
 }
 
 impl<T> Vec<T>
@@ -279,9 +350,7 @@ where
     ///
     /// This operation does not preserve ordering but is constant time.
     pub fn swap_remove(&mut self, n: u32) -> Option<T> {
-        if self.is_empty() {
-            return None
-        }
+        let n = self.within_bounds(n)?;
         self.elems.swap(n, self.len() - 1);
         self.pop()
     }
@@ -290,6 +359,7 @@ where
     ///
     /// The last element of the vector is put into the indexed slot.
     /// Returns `Some(())` if an element has been removed and `None` otherwise.
+    /// Does not mutate the vector if the index is out of bounds.
     ///
     /// # Note
     ///
@@ -297,9 +367,7 @@ where
     /// no need to return the removed element since it avoids a contract storage
     /// read for some use cases.
     pub fn swap_remove_drop(&mut self, n: u32) -> Option<()> {
-        if self.is_empty() {
-            return None
-        }
+        let n = self.within_bounds(n)?;
         self.elems.put(n, None);
         let last_index = self.len() - 1;
         let last = self.elems.put_get(last_index, None);
@@ -330,12 +398,101 @@ where
     /// This method performs significantly better and does not actually read
     /// any of the elements (whereas `pop()` does).
     pub fn clear(&mut self) {
-        if self.is_empty() {
+        self.truncate(0)
+    }
+
+    /// Sorts the vector.
+    ///
+    /// This sort is stable, i.e. it does not reorder equal elements.
+    ///
+    /// # Note
+    ///
+    /// The sort happens in memory and loads every element once from the
+    /// contract storage. Only elements that changed their position are
+    /// written back.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b))
+    }
+
+    /// Sorts the vector with a comparator function.
+    ///
+    /// See [`Vec::sort`] for the stability and performance characteristics.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let elems = &self.elems;
+        let load = |index: u32| {
+            elems
+                .get(index)
+                .expect("index is within bounds of the vector")
+        };
+        let mut indices = (0..self.len()).collect::<ink_prelude::vec::Vec<_>>();
+        indices.sort_by(|&a, &b| compare(load(a), load(b)));
+        // Take out all elements that are not yet at their sorted position
+        // before putting them into their new slots so that none is overwritten.
+        let mut moved = ink_prelude::vec::Vec::new();
+        for (new_index, old_index) in (0..).zip(indices) {
+            if new_index != old_index {
+                moved.push((new_index, self.elems.put_get(old_index, None)));
+            }
+        }
+        for (new_index, elem) in moved {
+            self.elems.put(new_index, elem);
+        }
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// Removes all elements `e` for which `f(&e)` returns `false`.
+    /// The predicate is called exactly once per element in their original order
+    /// and the order of the retained elements is preserved.
+    ///
+    /// # Note
+    ///
+    /// This loads every element once from the contract storage but only moved
+    /// or removed elements are written back.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let mut retained = 0;
+        for index in 0..len {
+            let elem = self
+                .elems
+                .get(index)
+                .expect("index is within bounds of the vector");
+            if !f(elem) {
+                continue
+            }
+            if retained != index {
+                let elem = self.elems.put_get(index, None);
+                self.elems.put(retained, elem);
+            }
+            retained += 1;
+        }
+        self.truncate(retained);
+    }
+
+    /// Shortens the vector, keeping the first `new_len` elements and removing the rest.
+    ///
+    /// Has no effect if `new_len` is greater than or equal to the vector's current length.
+    ///
+    /// # Note
+    ///
+    /// Same as [`Vec::clear`] this does not read any of the removed elements.
+    pub fn truncate(&mut self, new_len: u32) {
+        let len = self.len();
+        if new_len >= len {
             return
         }
-        for index in 0..self.len() {
+        for index in new_len..len {
             self.elems.put(index, None);
         }
-        *self.len = 0;
+        *self.len = new_len;
     }
 }
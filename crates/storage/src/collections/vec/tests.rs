@@ -357,6 +357,36 @@ fn swap_remove_drop_works() {
     assert_eq_slice(&vec, &[]);
 }
 
+#[test]
+fn swap_remove_out_of_bounds_works() {
+    let mut vec = vec_from_slice(&[b'a', b'b', b'c']);
+    assert_eq!(vec.swap_remove(3), None);
+    assert_eq_slice(&vec, &[b'a', b'b', b'c']);
+    assert_eq!(vec.swap_remove_drop(3), None);
+    assert_eq_slice(&vec, &[b'a', b'b', b'c']);
+    assert_eq!(vec.swap_remove(u32::MAX), None);
+    assert_eq!(vec.swap_remove_drop(u32::MAX), None);
+    assert_eq_slice(&vec, &[b'a', b'b', b'c']);
+}
+
+#[test]
+fn swap_remove_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let vec1 = vec_from_slice(&[b'a', b'b', b'c', b'd']);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        let mut vec2 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(vec2.swap_remove(0), Some(b'a'));
+        assert_eq!(vec2.swap_remove_drop(2), Some(()));
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        let vec3 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq_slice(&vec3, &[b'd', b'b']);
+        Ok(())
+    })
+}
+
 #[test]
 fn spread_layout_push_pull_works() -> ink_env::Result<()> {
     ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -425,3 +455,294 @@ fn clear_works_on_empty_vec() {
     vec.clear();
     assert!(vec.is_empty());
 }
+
+#[test]
+fn binary_search_works() {
+    let vec = vec_from_slice(&[1, 3, 5, 7, 9]);
+    assert_eq!(vec.binary_search(&1), Ok(0));
+    assert_eq!(vec.binary_search(&7), Ok(3));
+    assert_eq!(vec.binary_search(&9), Ok(4));
+    assert_eq!(vec.binary_search(&0), Err(0));
+    assert_eq!(vec.binary_search(&4), Err(2));
+    assert_eq!(vec.binary_search(&10), Err(5));
+    // Any of the duplicates may be found.
+    let vec = vec_from_slice(&[1, 2, 2, 2, 3]);
+    assert!(matches!(vec.binary_search(&2), Ok(1..=3)));
+    // Searching an empty vector yields the first index.
+    let vec = vec_from_slice(&[]);
+    assert_eq!(vec.binary_search(&1), Err(0));
+}
+
+#[test]
+fn binary_search_by_works() {
+    let vec = vec_from_slice(&[9, 7, 5, 3, 1]);
+    assert_eq!(vec.binary_search_by(|elem| 5.cmp(elem)), Ok(2));
+    assert_eq!(vec.binary_search_by(|elem| 6.cmp(elem)), Err(2));
+}
+
+#[test]
+fn insert_works() {
+    let mut vec = vec_from_slice(&[b'b', b'd']);
+    vec.insert(0, b'a');
+    assert_eq_slice(&vec, &[b'a', b'b', b'd']);
+    vec.insert(2, b'c');
+    assert_eq_slice(&vec, &[b'a', b'b', b'c', b'd']);
+    vec.insert(4, b'e');
+    assert_eq_slice(&vec, &[b'a', b'b', b'c', b'd', b'e']);
+}
+
+#[test]
+#[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+fn insert_out_of_bounds_fails() {
+    let mut vec = vec_from_slice(&[b'a']);
+    vec.insert(2, b'b');
+}
+
+#[test]
+fn insert_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let vec1 = vec_from_slice(&[10, 20, 30]);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        let mut vec2 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let index = vec2.binary_search(&25).unwrap_err();
+        vec2.insert(index, 25);
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        let vec3 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq_slice(&vec3, &[10, 20, 25, 30]);
+        assert_eq!(vec3.binary_search(&25), Ok(2));
+        Ok(())
+    })
+}
+
+#[test]
+fn truncate_works() {
+    let elems = [b'a', b'b', b'c', b'd'];
+    let mut vec = vec_from_slice(&elems);
+    // Truncating to a length greater or equal to the current length is a no-op.
+    vec.truncate(5);
+    assert_eq_slice(&vec, &elems);
+    vec.truncate(4);
+    assert_eq_slice(&vec, &elems);
+    vec.truncate(2);
+    assert_eq_slice(&vec, &elems[0..2]);
+    assert_eq!(vec.get(2), None);
+    vec.truncate(0);
+    assert_eq_slice(&vec, &[]);
+}
+
+#[test]
+fn truncate_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let vec1 = vec_from_slice(&[b'a', b'b', b'c', b'd']);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        let mut vec2 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        vec2.truncate(1);
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        let vec3 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq_slice(&vec3, &[b'a']);
+        assert_eq!(vec3.get(1), None);
+        // The elements are stored right after the length field.
+        for index in 1..4 {
+            let elem_key = root_key + 1 + index;
+            assert_eq!(ink_env::get_contract_storage::<u8>(&elem_key), Ok(None));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn sort_works() {
+    let test_inputs: &[&[u8]] = &[
+        &[],
+        &[1],
+        &[1, 2, 3, 4, 5],
+        &[5, 4, 3, 2, 1],
+        &[3, 1, 3, 2, 1, 3, 2, 1],
+        &[2, 2, 2, 2],
+    ];
+    for input in test_inputs {
+        let mut vec = vec_from_slice(input);
+        vec.sort();
+        let mut expected = input.to_vec();
+        expected.sort();
+        assert_eq_slice(&vec, &expected);
+    }
+}
+
+#[test]
+fn sort_by_is_stable() {
+    let mut vec = [(2, b'a'), (1, b'b'), (2, b'c'), (1, b'd')]
+        .iter()
+        .copied()
+        .collect::<StorageVec<(u8, u8)>>();
+    vec.sort_by(|a, b| a.0.cmp(&b.0));
+    assert!(vec
+        .iter()
+        .eq([(1, b'b'), (1, b'd'), (2, b'a'), (2, b'c')].iter()));
+    // Reverse sort order.
+    vec.sort_by(|a, b| b.cmp(a));
+    assert!(vec
+        .iter()
+        .eq([(2, b'c'), (2, b'a'), (1, b'd'), (1, b'b')].iter()));
+}
+
+#[test]
+fn sort_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let vec1 = vec_from_slice(&[1, 5, 3, 4, 2]);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let mut vec2 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let (_, base_writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        vec2.sort();
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        let (_, writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        // Only the elements at indices 1 and 4 changed their position.
+        assert_eq!(writes - base_writes, 2);
+        let vec3 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq_slice(&vec3, &[1, 2, 3, 4, 5]);
+        Ok(())
+    })
+}
+
+#[test]
+fn retain_works() {
+    let elems = [b'a', b'b', b'c', b'd', b'e'];
+    // Retain all elements.
+    let mut vec = vec_from_slice(&elems);
+    vec.retain(|_| true);
+    assert_eq_slice(&vec, &elems);
+    // Retain no elements.
+    let mut vec = vec_from_slice(&elems);
+    vec.retain(|_| false);
+    assert_eq_slice(&vec, &[]);
+    // Retain some elements and check the predicate is called in order.
+    let mut vec = vec_from_slice(&elems);
+    let mut visited = vec![];
+    vec.retain(|elem| {
+        visited.push(*elem);
+        *elem != b'a' && *elem != b'c'
+    });
+    assert_eq!(visited, elems);
+    assert_eq_slice(&vec, &[b'b', b'd', b'e']);
+    assert_eq!(vec.get(3), None);
+}
+
+#[test]
+fn retain_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let vec1 = vec_from_slice(&[1, 2, 3, 4, 5, 6]);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        // Retaining all elements does not write to the contract storage.
+        let mut vec2 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let (_, base_writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        vec2.retain(|_| true);
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        let (_, writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        assert_eq!(writes, base_writes);
+        core::mem::forget(vec2);
+        // Retain only the even elements.
+        let mut vec3 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        vec3.retain(|elem| elem % 2 == 0);
+        SpreadLayout::push_spread(&vec3, &mut KeyPtr::from(root_key));
+        core::mem::forget(vec3);
+        let vec4 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq_slice(&vec4, &[2, 4, 6]);
+        // The elements are stored right after the length field.
+        for index in 3..6 {
+            let elem_key = root_key + 1 + index;
+            assert_eq!(ink_env::get_contract_storage::<u8>(&elem_key), Ok(None));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn read_only_access_causes_no_writes() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let vec1 = (0..100).collect::<StorageVec<u32>>();
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let vec2 =
+            <StorageVec<u32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let (_, base_writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        assert_eq!(vec2.iter().copied().sum::<u32>(), (0..100).sum());
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        let (_, writes) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        // Only mutated cache entries are written back to the contract storage.
+        assert_eq!(writes, base_writes);
+        Ok(())
+    })
+}
+
+#[test]
+fn clear_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let vec1 = vec_from_slice(&[b'a', b'b', b'c', b'd']);
+        let root_key = Key::from([0x42; 32]);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        // The elements are stored right after the length field.
+        let elem_key = |index: u64| root_key + 1 + index;
+        assert_eq!(
+            ink_env::get_contract_storage::<u8>(&elem_key(3)),
+            Ok(Some(b'd'))
+        );
+        let contract_account = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let mut vec2 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(vec2.len(), 4);
+        let (base_reads, _) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        vec2.clear();
+        let (reads, _) = ink_env::test::get_contract_storage_rw::<
+            ink_env::DefaultEnvironment,
+        >(&contract_account)?;
+        // Clearing must not load the elements just to delete them.
+        assert_eq!(reads, base_reads);
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        // No residual element entries remain in the contract storage.
+        let vec3 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(vec3.is_empty());
+        for index in 0..4 {
+            assert_eq!(ink_env::get_contract_storage::<u8>(&elem_key(index)), Ok(None));
+        }
+        Ok(())
+    })
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::{
+    checked_offset_key,
     CacheCell,
     EntryState,
     StorageEntry,
@@ -443,7 +444,7 @@ where
         let offset_key = ExtKeyPtr::next_for::<Self>(ptr);
         for (index, entry) in self.cached_entries().iter().enumerate() {
             if let Some(entry) = entry {
-                let key = offset_key + (index as u64);
+                let key = checked_offset_key(offset_key, index as u64);
                 entry.push_packed_root(&key);
             }
         }
@@ -467,7 +468,9 @@ where
         if at >= self.capacity() {
             return None
         }
-        self.key.as_ref().map(|key| key + at as u64)
+        self.key
+            .as_ref()
+            .map(|key| checked_offset_key(key, at as u64))
     }
 }
 
@@ -669,6 +672,20 @@ mod tests {
         assert_cached_entries(&larray, &[]);
     }
 
+    #[test]
+    #[should_panic(expected = "encountered key overflow for offset 3")]
+    fn key_at_overflow_panics() {
+        let max_key = Key::from([0xFF; 32]);
+        let key = Key::from({
+            let mut bytes = [0xFF; 32];
+            bytes[0..8].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+            bytes
+        });
+        let larray = <LazyArray<u8, U4>>::lazy(key);
+        assert_eq!(larray.key_at(1), Some(max_key));
+        let _ = larray.key_at(3);
+    }
+
     #[test]
     fn get_works() {
         let mut larray = <LazyArray<u8, U4>>::new();
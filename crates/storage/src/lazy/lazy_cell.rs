@@ -311,6 +311,41 @@ where
         self.load_entry_mut().value_mut().into()
     }
 
+    /// Returns a shared reference to the value or to its default if the cell is empty.
+    ///
+    /// # Note
+    ///
+    /// This eventually lazily loads the value from the contract storage.
+    /// The default value is only put into the cache and is not written back
+    /// to the contract storage unless it is mutated, e.g. via
+    /// [`LazyCell::get_mut`]. Afterwards [`LazyCell::get`] yields the default.
+    ///
+    /// # Panics
+    ///
+    /// If decoding the loaded value to `T` failed.
+    #[must_use]
+    pub fn get_or_default(&self) -> &T
+    where
+        T: Default,
+    {
+        // SAFETY: We load the entry either from cache of from contract storage.
+        //
+        //         This is critical because we mutably access the entry from
+        //         within a `&self` method. However, we only mutate the entry
+        //         if it is vacant in which case no references to its value
+        //         can exist. The state of the entry is preserved so that the
+        //         default value does not get written to the contract storage.
+        let entry = unsafe { &mut *self.load_through_cache().as_ptr() };
+        if entry.value().is_none() {
+            // Accessing a vacant entry does not mark it as mutated.
+            *entry.value_mut() = Some(Default::default());
+        }
+        entry
+            .value()
+            .as_ref()
+            .expect("encountered empty cache entry after populating it")
+    }
+
     /// Sets the value in this cell to `value`, without executing any reads.
     ///
     /// # Note
@@ -339,6 +374,45 @@ where
         }
         debug_assert!(cache.is_some());
     }
+
+    /// Takes the value out of this cell, leaving it empty.
+    ///
+    /// # Note
+    ///
+    /// This eventually lazily loads the value from the contract storage.
+    /// The underlying storage cell is cleared upon the next push.
+    ///
+    /// # Panics
+    ///
+    /// If decoding the loaded value to `T` failed.
+    pub fn take(&mut self) -> Option<T> {
+        self.put(None)
+    }
+
+    /// Replaces the value in this cell with `new_value` and returns the old value.
+    ///
+    /// # Note
+    ///
+    /// This eventually lazily loads the value from the contract storage.
+    /// Prefer [`LazyCell::set`] if the old value is of no interest to the caller.
+    ///
+    /// # Panics
+    ///
+    /// If decoding the loaded value to `T` failed.
+    pub fn replace(&mut self, new_value: T) -> Option<T> {
+        self.put(Some(new_value))
+    }
+
+    /// Puts the new value into the loaded entry and returns the old value.
+    fn put(&mut self, new_value: Option<T>) -> Option<T> {
+        // SAFETY: We load the entry either from cache of from contract storage.
+        //
+        //         This is safe because we are just returning an exclusive reference
+        //         from within a `&mut self` method. The `mutate` flag of the entry
+        //         is updated by `StorageEntry::put` depending on the old and new values.
+        let entry = unsafe { &mut *self.load_through_cache().as_ptr() };
+        entry.put(new_value)
+    }
 }
 
 #[cfg(test)]
@@ -414,6 +488,101 @@ mod tests {
         assert_eq!(cell.get(), Some(&2));
     }
 
+    #[test]
+    fn lazy_get_mut_persists_mutation() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let cell = <LazyCell<i32>>::new(Some(1));
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            let contract_account = ink_env::test::get_current_contract_account_id::<
+                ink_env::DefaultEnvironment,
+            >()?;
+            {
+                let mut pulled =
+                    <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(
+                        root_key,
+                    ));
+                *pulled.get_mut().unwrap() += 1;
+                let (base_reads, _) = ink_env::test::get_contract_storage_rw::<
+                    ink_env::DefaultEnvironment,
+                >(&contract_account)?;
+                *pulled.get_mut().unwrap() += 1;
+                let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                    ink_env::DefaultEnvironment,
+                >(&contract_account)?;
+                // Repeated `get_mut` calls are served from the cache.
+                assert_eq!(reads, base_reads);
+                assert_eq!(
+                    pulled.entry(),
+                    Some(&StorageEntry::new(Some(3), EntryState::Mutated))
+                );
+                SpreadLayout::push_spread(&pulled, &mut KeyPtr::from(root_key));
+                // Prevent the intermediate instance from clearing the storage.
+                core::mem::forget(pulled);
+            }
+            let reloaded = <LazyCell<i32>>::lazy(root_key);
+            assert_eq!(reloaded.get(), Some(&3));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn get_or_default_works() {
+        let cell = <LazyCell<i32>>::new(Some(1));
+        assert_eq!(cell.get_or_default(), &1);
+        let empty = <LazyCell<i32>>::new(None);
+        assert_eq!(empty.get_or_default(), &0);
+        assert_eq!(empty.get(), Some(&0));
+    }
+
+    #[test]
+    fn lazy_get_or_default_does_not_write() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let contract_account = ink_env::test::get_current_contract_account_id::<
+                ink_env::DefaultEnvironment,
+            >()?;
+            let cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell.get_or_default(), &0);
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(Some(0), EntryState::Preserved))
+            );
+            let (_, base_writes) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&contract_account)?;
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            let (_, writes) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&contract_account)?;
+            // The cached default value is not flushed to the contract storage.
+            assert_eq!(writes, base_writes);
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(None));
+            // Prevent the instance from clearing the storage.
+            core::mem::forget(cell);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn lazy_get_or_default_persists_mutation() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let mut lazy =
+                <Lazy<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(Lazy::get_or_default(&lazy), &0);
+            *Lazy::get_mut(&mut lazy) += 5;
+            SpreadLayout::push_spread(&lazy, &mut KeyPtr::from(root_key));
+            // Prevent the intermediate instance from clearing the storage.
+            core::mem::forget(lazy);
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(Some(5)));
+            let reloaded = <LazyCell<i32>>::lazy(root_key);
+            assert_eq!(reloaded.get(), Some(&5));
+            Ok(())
+        })
+    }
+
     #[test]
     fn spread_layout_works() -> ink_env::Result<()> {
         run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -459,6 +628,59 @@ mod tests {
         assert_eq!(cell.get(), Some(&23));
     }
 
+    #[test]
+    fn take_works() {
+        let mut cell = <LazyCell<i32>>::new(Some(1));
+        assert_eq!(cell.take(), Some(1));
+        assert_eq!(cell.get(), None);
+        assert_eq!(cell.take(), None);
+        // Taking from an empty cell does not mark it as mutated.
+        let mut empty = <LazyCell<i32>>::new(None);
+        empty.load_entry().replace_state(EntryState::Preserved);
+        assert_eq!(empty.take(), None);
+        assert_eq!(
+            empty.entry(),
+            Some(&StorageEntry::new(None, EntryState::Preserved))
+        );
+    }
+
+    #[test]
+    fn replace_works() {
+        let mut cell = <LazyCell<i32>>::new(None);
+        assert_eq!(cell.replace(1), None);
+        assert_eq!(cell.replace(2), Some(1));
+        assert_eq!(cell.get(), Some(&2));
+    }
+
+    #[test]
+    fn take_and_replace_work_with_spread_layout_push_pull() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            SpreadLayout::push_spread(
+                &<LazyCell<i32>>::new(Some(1)),
+                &mut KeyPtr::from(root_key),
+            );
+            // Replace the value and push it back to the contract storage.
+            let mut cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell.replace(2), Some(1));
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            core::mem::forget(cell);
+            // Take the value and push the now empty cell to the contract storage.
+            let mut cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell.take(), Some(2));
+            assert_eq!(cell.get(), None);
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            core::mem::forget(cell);
+            // The underlying storage cell has been cleared.
+            let cell = <LazyCell<i32>>::lazy(root_key);
+            assert_eq!(cell.get(), None);
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(None));
+            Ok(())
+        })
+    }
+
     #[test]
     fn lazy_set_works() -> ink_env::Result<()> {
         run_test::<ink_env::DefaultEnvironment, _>(|_| {
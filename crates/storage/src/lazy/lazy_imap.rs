@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::{
+    checked_offset_key,
     CacheCell,
     EntryState,
     StorageEntry,
@@ -225,7 +226,7 @@ const _: () = {
         fn layout(key_ptr: &mut KeyPtr) -> Layout {
             let capacity = u32::MAX;
             Layout::Array(ArrayLayout::new(
-                LayoutKey::from(key_ptr.advance_by(capacity as u64)),
+                LayoutKey::from(key_ptr.advance_by(1_u64 << 32)),
                 capacity,
                 1,
                 Layout::Cell(CellLayout::new::<T>(LayoutKey::from(
@@ -249,7 +250,7 @@ where
     fn push_spread(&self, ptr: &mut KeyPtr) {
         let offset_key = ExtKeyPtr::next_for::<Self>(ptr);
         for (&index, entry) in self.entries().iter() {
-            let root_key = offset_key + (index as u64);
+            let root_key = checked_offset_key(offset_key, index as u64);
             entry.push_packed_root(&root_key);
         }
     }
@@ -299,9 +300,8 @@ where
 {
     /// Returns an offset key for the given index.
     pub fn key_at(&self, index: Index) -> Option<Key> {
-        let key = self.key?;
-        let offset_key = key + index as u64;
-        Some(offset_key)
+        let key = self.key.as_ref()?;
+        Some(checked_offset_key(key, index as u64))
     }
 
     /// Lazily loads the value at the given index.
@@ -504,6 +504,15 @@ mod tests {
         assert_cached_entries(&imap, &[]);
     }
 
+    #[test]
+    #[should_panic(expected = "encountered key overflow for offset 1")]
+    fn key_at_overflow_panics() {
+        let key = Key::from([0xFF; 32]);
+        let imap = <LazyIndexMap<u8>>::lazy(key);
+        assert_eq!(imap.key_at(0), Some(key));
+        let _ = imap.key_at(1);
+    }
+
     #[test]
     fn put_get_works() {
         let mut imap = <LazyIndexMap<u8>>::new();
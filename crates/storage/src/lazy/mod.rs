@@ -55,6 +55,20 @@ use crate::traits::{
 };
 use ink_primitives::Key;
 
+/// Returns the key of the storage cell at the given offset from the root key.
+///
+/// # Panics
+///
+/// If the offset key wraps around the 256-bit key space.
+fn checked_offset_key(root_key: &Key, offset: u64) -> Key {
+    root_key.checked_add(offset).unwrap_or_else(|| {
+        panic!(
+            "encountered key overflow for offset {} from root key {}",
+            offset, root_key,
+        )
+    })
+}
+
 /// A lazy storage entity.
 ///
 /// This loads its value from storage upon first use.
@@ -159,6 +173,26 @@ where
         lazy.cell.get_mut().expect("encountered empty storage cell")
     }
 
+    /// Returns a shared reference to the lazily loaded value or to its default.
+    ///
+    /// # Note
+    ///
+    /// This loads the value from the contract storage if this did not happen before.
+    /// If the storage cell is empty its default value is returned instead. The
+    /// default value is not written back to the contract storage unless it is
+    /// mutated afterwards.
+    ///
+    /// # Panics
+    ///
+    /// If loading from contract storage failed.
+    #[must_use]
+    pub fn get_or_default(lazy: &Self) -> &T
+    where
+        T: Default,
+    {
+        lazy.cell.get_or_default()
+    }
+
     /// Sets the value to `value`, without executing any reads.
     ///
     /// # Note
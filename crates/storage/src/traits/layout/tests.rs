@@ -11,3 +11,90 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+
+use crate::{
+    collections::Vec as StorageVec,
+    lazy::Lazy,
+    traits::{
+        pull_spread_root,
+        push_spread_root,
+        KeyPtr,
+        SpreadLayout,
+        StorageLayout,
+    },
+};
+use ink_metadata::layout::{
+    CellLayout,
+    Layout,
+    LayoutKey,
+};
+use ink_primitives::Key;
+
+/// The root fields of a contract storage, simulated by a tuple.
+type Root = (Lazy<u32>, StorageVec<u8>, Lazy<bool>);
+
+/// The same root fields as `Root` in a different order.
+type ReorderedRoot = (StorageVec<u8>, Lazy<u32>, Lazy<bool>);
+
+fn root_key() -> Key {
+    Key::from([0x42; 32])
+}
+
+#[test]
+fn layout_assigns_consecutive_keys() {
+    let layout = <Root as StorageLayout>::layout(&mut KeyPtr::from(root_key()));
+    let fields = match &layout {
+        Layout::Struct(layout) => layout.fields(),
+        _ => panic!("expected a struct layout for the root fields"),
+    };
+    assert_eq!(fields.len(), 3);
+    assert_eq!(
+        fields[0].layout(),
+        &Layout::Cell(CellLayout::new::<u32>(LayoutKey::from(root_key())))
+    );
+    // The storage vector occupies the key of its length followed by the
+    // keys of its elements so the last field is placed behind it.
+    let vec_footprint = <StorageVec<u8> as SpreadLayout>::FOOTPRINT;
+    assert_eq!(
+        fields[2].layout(),
+        &Layout::Cell(CellLayout::new::<bool>(LayoutKey::from(
+            root_key() + (1 + vec_footprint)
+        )))
+    );
+}
+
+#[test]
+fn layout_is_identical_across_calls() {
+    let deploy = <Root as StorageLayout>::layout(&mut KeyPtr::from(root_key()));
+    let call = <Root as StorageLayout>::layout(&mut KeyPtr::from(root_key()));
+    assert_eq!(deploy, call);
+}
+
+#[test]
+fn reordered_fields_are_detected_as_layout_mismatch() {
+    let original = <Root as StorageLayout>::layout(&mut KeyPtr::from(root_key()));
+    let reordered =
+        <ReorderedRoot as StorageLayout>::layout(&mut KeyPtr::from(root_key()));
+    assert_ne!(original, reordered);
+}
+
+#[test]
+fn call_reconstructs_the_deployed_fields() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        // Deploy: construct the root fields and push them to their keys.
+        let deployed: Root = (
+            Lazy::new(42),
+            [b'A', b'B', b'C'].iter().copied().collect(),
+            Lazy::new(true),
+        );
+        push_spread_root(&deployed, &root_key());
+        // Call: pull the root fields from the same keys without constructing
+        // them from scratch.
+        let called = pull_spread_root::<Root>(&root_key());
+        assert_eq!(*called.0, 42);
+        assert!(called.1.iter().eq([b'A', b'B', b'C'].iter()));
+        assert!(*called.2);
+        core::mem::forget((deployed, called));
+        Ok(())
+    })
+}